@@ -1,11 +1,8 @@
 use eframe::egui;
 use eframe::egui::Style;
 use egui_stylist::{StylistFileDialog, StylistState};
-use std::fs::File;
-use std::io::Read;
-#[cfg(not(target_arch = "wasm32"))]
-use std::io::Write;
-use std::path::PathBuf;
+use egui_theme::{EguiTheme, ThemeDiff, ThemeError, ThemeFormat};
+use std::path::{Path, PathBuf};
 
 // fn open_error_window(ctx: &egui::Context, title: &str, text: &str, open: &mut bool) {
 //     let window = egui::Window::new(title.to_owned())
@@ -27,6 +24,14 @@ pub struct StylistApp {
     state: StylistState,
     show_error_window: bool,
     error_msg: String,
+    /// A save that is waiting for the user to confirm overwriting an existing theme, or a file that is not a readable theme.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pending_save: Option<(PathBuf, EguiTheme, Result<ThemeDiff, ThemeError>)>,
+    show_subset_window: bool,
+    /// Keep Basic Latin and the Latin-1 Supplement when saving a subset theme.
+    subset_latin1: bool,
+    /// Additional characters to keep when saving a subset theme.
+    subset_chars: String,
 }
 
 impl StylistApp {
@@ -70,6 +75,137 @@ impl Default for StylistApp {
             state: StylistState::default(),
             show_error_window: false,
             error_msg: "".to_owned(),
+            pending_save: None,
+            show_subset_window: false,
+            subset_latin1: true,
+            subset_chars: "".to_owned(),
+        }
+    }
+}
+
+impl StylistApp {
+    /// Compares the theme with the file already at `path`, if there is one.
+    /// Fails if the file exists but cannot be loaded as a theme.
+    fn existing_theme_diff(
+        path: &Path,
+        theme: &EguiTheme,
+    ) -> Option<Result<ThemeDiff, ThemeError>> {
+        if !path.exists() {
+            return None;
+        }
+        let diff = EguiTheme::load(path).map(|existing| egui_theme::diff_themes(&existing, theme));
+        Some(diff)
+    }
+
+    /// Saves the theme, asking before overwriting a different theme or any other file at `path`.
+    fn request_save(&mut self, path: PathBuf, theme: EguiTheme) {
+        match Self::existing_theme_diff(&path, &theme) {
+            Some(Ok(diff)) if diff.is_empty() => self.save_theme(path, &theme),
+            Some(diff) => self.pending_save = Some((path, theme, diff)),
+            None => self.save_theme(path, &theme),
+        }
+    }
+
+    fn save_theme(&mut self, path: PathBuf, theme: &EguiTheme) {
+        if let Err(err) = theme.save(path) {
+            self.error_msg = format!("Saving failed with {}", err);
+            self.show_error_window = true;
+        }
+    }
+
+    /// Shows the differences with the theme on disk and asks before overwriting it.
+    fn confirm_save_window(&mut self, ctx: &egui::Context) {
+        let mut overwrite = None;
+        if let Some((path, _, diff)) = self.pending_save.as_ref() {
+            egui::Window::new("Overwrite theme?")
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::new(0.0, 0.0))
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    match diff {
+                        Ok(diff) => {
+                            ui.label(format!(
+                                "`{}` already exists and differs from the current theme:",
+                                path.display()
+                            ));
+                            egui::ScrollArea::vertical()
+                                .max_height(300.0)
+                                .show(ui, |ui| {
+                                    for change in diff.changes.iter() {
+                                        ui.monospace(change.to_string());
+                                    }
+                                });
+                        }
+                        Err(err) => {
+                            ui.label(format!(
+                                "`{}` already exists but cannot be compared with the current theme:",
+                                path.display()
+                            ));
+                            ui.colored_label(egui::Color32::RED, err.to_string());
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Overwrite").clicked() {
+                            overwrite = Some(true);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            overwrite = Some(false);
+                        }
+                    });
+                });
+        }
+        if let Some(overwrite) = overwrite {
+            if let Some((path, theme, _)) = self.pending_save.take() {
+                if overwrite {
+                    self.save_theme(path, &theme);
+                }
+            }
+        }
+    }
+
+    /// Options for saving a theme whose fonts only keep the glyphs of the selected characters.
+    fn subset_window(&mut self, ctx: &egui::Context) {
+        let mut save = false;
+        let latin1 = &mut self.subset_latin1;
+        let chars = &mut self.subset_chars;
+        egui::Window::new("Save Subset")
+            .open(&mut self.show_subset_window)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Embedded fonts only keep the glyphs of these characters.");
+                ui.checkbox(latin1, "Latin-1");
+                ui.horizontal(|ui| {
+                    ui.label("Additional characters");
+                    ui.text_edit_singleline(chars);
+                });
+                save = ui.button("Save").clicked();
+            });
+        if !save {
+            return;
+        }
+        let path = match self.state.file_dialog(
+            StylistFileDialog::Save,
+            Some(("eguitheme", ThemeFormat::extensions())),
+        ) {
+            Some(path) => path,
+            None => return,
+        };
+        let latin1 = self
+            .subset_latin1
+            .then(egui_theme::latin1_chars)
+            .into_iter()
+            .flatten();
+        match self
+            .state
+            .export_subset_theme(latin1.chain(self.subset_chars.chars()))
+        {
+            Ok(theme) => {
+                self.show_subset_window = false;
+                self.request_save(path, theme);
+            }
+            Err(err) => {
+                self.error_msg = format!("Subsetting the fonts failed with {}", err);
+                self.show_error_window = true;
+            }
         }
     }
 }
@@ -162,57 +298,27 @@ impl eframe::App for StylistApp {
                         // Option a popup to save the file to a given directory
                         if let Some(path) = self.state.file_dialog(
                             StylistFileDialog::Save,
-                            Some(("eguitheme", &["ron", "eguitheme"])),
+                            Some(("eguitheme", ThemeFormat::extensions())),
                         ) {
                             let theme = self.state.export_theme();
-                            match ron::to_string(&theme) {
-                                Ok(value) => match File::create(path) {
-                                    Ok(mut f) => {
-                                        if let Err(err) = f.write_all(value.as_bytes()) {
-                                            self.error_msg = format!("Saving failed with {}", err);
-                                            self.show_error_window = true;
-                                        }
-                                    }
-                                    Err(err) => {
-                                        self.error_msg =
-                                            format!("Creating file failed with {}", err);
-                                        self.show_error_window = true;
-                                    }
-                                },
-                                Err(err) => {
-                                    self.error_msg =
-                                        format!("Opening file path failed with {}", err);
-                                    self.show_error_window = true;
-                                }
-                            }
+                            self.request_save(path, theme);
                         }
                     }
+                    if ui.button("Save Subset...").clicked() {
+                        self.show_subset_window = true;
+                    }
                     if ui.button("Load").clicked() {
                         if let Some(path) = self.state.file_dialog(
                             StylistFileDialog::Open,
-                            Some(("eguitheme", &["ron", "eguitheme"])),
+                            Some(("eguitheme", ThemeFormat::extensions())),
                         ) {
-                            match File::open(path) {
-                                Ok(mut f) => {
-                                    let mut buf = String::new();
-                                    f.read_to_string(&mut buf).expect("this should work");
-                                    match ron::from_str(&buf) {
-                                        Ok(theme) => self.state.import_theme(theme),
-                                        Err(err) => {
-                                            self.error_msg = format!(
-                                                "Loading theme failed with the following error {}",
-                                                err
-                                            );
-                                            self.show_error_window = true;
-                                        }
-                                    }
-                                }
+                            match EguiTheme::load(path) {
+                                Ok(theme) => self.state.import_theme(theme),
                                 Err(err) => {
                                     self.error_msg = format!(
                                         "Loading theme failed with the following error {}",
                                         err
                                     );
-                                    self.error_msg = format!("Failed to open file due to: {}", err);
                                     self.show_error_window = true;
                                 }
                             }
@@ -242,6 +348,8 @@ impl eframe::App for StylistApp {
             });
         });
 
+        self.subset_window(ctx);
+        self.confirm_save_window(ctx);
         egui::CentralPanel::default().show(ctx, |ui| self.state.ui(ui));
     }
 }
@@ -1,27 +1,77 @@
 use super::StylistFileDialog;
-use std::io::Read;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 use egui::FontData;
+use egui_theme::{FontError, FontInfo, SystemFont, ThemeIssue, UNICODE_RANGES};
 
-// TODO: Reference egui-theme which is where this info should be stored.
-const DEFAULT_FONTS: [&str; 4] = [
-    "Hack",
-    "Ubuntu-Light",
-    "NotoEmoji-Regular",
-    "emoji-icon-font",
-];
+use egui::text::{LayoutJob, TextFormat};
 use egui::{
-    Button, Checkbox, CollapsingHeader, ComboBox, DragValue, FontDefinitions, FontFamily, Grid,
-    Label, Style, TextEdit, TextStyle, Ui, Widget,
+    Button, Checkbox, CollapsingHeader, Color32, ComboBox, CursorIcon, DragValue, FontDefinitions,
+    FontFamily, FontId, FontTweak, Grid, Id, Label, LayerId, Order, Rect, RichText, ScrollArea,
+    Sense, Stroke, Style, TextEdit, TextStyle, Ui, Widget,
 };
 
+/// Prefix of the families that are added while editing so every font can be previewed on its own.
+const PREVIEW_FAMILY_PREFIX: &str = "__preview_";
+/// The name the previewed system font is loaded under, it is never added to the font definitions being edited.
+const SYSTEM_PREVIEW_FONT: &str = "__system_preview";
+
 pub struct FontViewState {
     to_add_name: String,
     to_add_path: String,
     to_add_family: String,
     to_delete: Vec<String>,
+    /// A font or family that is still in use and waits for the deletion to be confirmed.
+    pending_font_delete: Option<String>,
+    pending_family_delete: Option<FontFamily>,
+    /// The family that text styles using the deleted family are moved to.
+    replacement_family: FontFamily,
+    /// Why the pending family could not be deleted.
+    family_delete_error: Option<String>,
+    /// Why the last font could not be added.
+    add_error: Option<String>,
+    /// Font issues found while importing the last theme.
+    pub(crate) load_issues: Vec<ThemeIssue>,
+    /// The parsed metadata of every installed font, keyed by the font name.
+    font_info: HashMap<String, CachedFontInfo>,
+    /// The sample text checked by the glyph coverage panel.
+    coverage_text: String,
+    /// Which of `UNICODE_RANGES` are checked by the glyph coverage panel.
+    coverage_ranges: Vec<bool>,
+    /// The characters each family is missing, from the last time the coverage was checked.
+    missing_glyphs: Option<BTreeMap<FontFamily, Vec<char>>>,
+    /// The text drawn by the font preview panel.
+    preview_text: String,
+    /// The sizes, in points, the font preview panel draws the text at.
+    preview_sizes: Vec<f32>,
+    /// The font whose tweak is being edited.
+    tweak_font: Option<String>,
+    /// The font that is being dragged in the font priority editor.
+    dragged_font: Option<DraggedFont>,
+    /// The font or family selected for renaming and its new name.
+    rename_font: Option<String>,
+    rename_font_to: String,
+    rename_family: Option<String>,
+    rename_family_to: String,
+    /// Why the last rename failed.
+    rename_error: Option<String>,
+    /// The fonts installed on the host, `None` until they are scanned.
+    system_fonts: Option<Vec<SystemFont>>,
+    /// Receives the installed fonts while they are scanned in the background.
+    system_font_scan: Option<Receiver<Vec<SystemFont>>>,
+    /// Only system fonts whose family name contains this are listed.
+    system_font_search: String,
+    /// The system font shown in the preview and its data.
+    system_font_preview: Option<(SystemFont, FontData)>,
+    /// Why the last system font could not be previewed or added.
+    system_font_error: Option<String>,
     pub(crate) pixels_per_point: f32,
+    /// Whether the fonts being edited are given to the context, fonts can only be drawn on their own while it is.
+    pub(crate) preview_enabled: bool,
 }
 
 impl Default for FontViewState {
@@ -31,9 +81,146 @@ impl Default for FontViewState {
             to_add_path: "".to_owned(),
             to_add_family: "".to_owned(),
             to_delete: Vec::new(),
+            pending_font_delete: None,
+            pending_family_delete: None,
+            replacement_family: FontFamily::Proportional,
+            family_delete_error: None,
+            add_error: None,
+            load_issues: Vec::new(),
+            font_info: HashMap::new(),
+            coverage_text: "".to_owned(),
+            coverage_ranges: vec![false; UNICODE_RANGES.len()],
+            missing_glyphs: None,
+            preview_text: "The quick brown fox jumps over the lazy dog 0123456789".to_owned(),
+            preview_sizes: vec![12.0, 16.0, 24.0],
+            tweak_font: None,
+            dragged_font: None,
+            rename_font: None,
+            rename_font_to: "".to_owned(),
+            rename_family: None,
+            rename_family_to: "".to_owned(),
+            rename_error: None,
+            system_fonts: None,
+            system_font_scan: None,
+            system_font_search: "".to_owned(),
+            system_font_preview: None,
+            system_font_error: None,
             pixels_per_point: 1f32,
+            preview_enabled: true,
+        }
+    }
+}
+
+/// Parsing the font is expensive for large fonts, so the metadata is cached until the font data changes.
+struct CachedFontInfo {
+    /// The address, length and face index of the font data the info was parsed from.
+    key: (usize, usize, u32),
+    info: Result<FontInfo, FontError>,
+}
+
+impl FontViewState {
+    fn font_info(&mut self, name: &str, data: &FontData) -> &Result<FontInfo, FontError> {
+        let key = (data.font.as_ptr() as usize, data.font.len(), data.index);
+        let cached = self
+            .font_info
+            .get(name)
+            .is_some_and(|cached| cached.key == key);
+        if !cached {
+            let info = FontInfo::parse(&data.font, data.index);
+            self.font_info
+                .insert(name.to_owned(), CachedFontInfo { key, info });
+        }
+        &self.font_info[name].info
+    }
+
+    /// Keeps the state that refers to the font `from` pointing at it once it is renamed to `to`.
+    fn font_renamed(&mut self, from: &str, to: &str) {
+        if let Some(info) = self.font_info.remove(from) {
+            self.font_info.insert(to.to_owned(), info);
+        }
+        for name in self
+            .tweak_font
+            .iter_mut()
+            .chain(self.pending_font_delete.iter_mut())
+            .chain(
+                self.dragged_font
+                    .iter_mut()
+                    .map(|dragged| &mut dragged.name),
+            )
+            .chain(self.to_delete.iter_mut())
+        {
+            if name == from {
+                *name = to.to_owned();
+            }
         }
     }
+
+    /// Keeps the state that refers to the family `from` pointing at it once it is renamed to `to`.
+    fn family_renamed(&mut self, from: &FontFamily, to: &FontFamily) {
+        for family in self
+            .pending_family_delete
+            .iter_mut()
+            .chain(Some(&mut self.replacement_family))
+            .chain(
+                self.dragged_font
+                    .iter_mut()
+                    .filter_map(|dragged| dragged.from.as_mut().map(|(family, _)| family)),
+            )
+        {
+            if family == from {
+                *family = to.to_owned();
+            }
+        }
+        if let Some(missing_glyphs) = &mut self.missing_glyphs {
+            if let Some(missing) = missing_glyphs.remove(from) {
+                missing_glyphs.insert(to.to_owned(), missing);
+            }
+        }
+    }
+
+    /// Drops the cached metadata of fonts that are no longer installed, such as deleted or renamed fonts.
+    fn prune_font_info(&mut self, font_definitions: &FontDefinitions) {
+        self.font_info
+            .retain(|name, _| font_definitions.font_data.contains_key(name));
+    }
+}
+
+fn font_info_row(info: &FontInfo, ui: &mut Ui) {
+    ui.label(info.family_name.as_deref().unwrap_or("Unknown"));
+    let mut style = info.style_name.clone().unwrap_or_default();
+    style.push_str(&format!(" ({})", info.weight));
+    if info.monospaced {
+        style.push_str(", monospaced");
+    }
+    ui.label(style);
+    ui.label(info.glyph_count.to_string());
+    ui.label(format!("{:.1} KiB", info.file_size as f32 / 1024.0));
+    let scripts = info
+        .coverage
+        .iter()
+        .map(|coverage| coverage.range.name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    ui.add(Label::new(scripts).wrap(true)).on_hover_ui(|ui| {
+        Grid::new("_font_coverage").num_columns(2).show(ui, |ui| {
+            for coverage in info.coverage.iter() {
+                ui.label(coverage.range.name);
+                ui.label(format!("{} / {}", coverage.covered, coverage.range.count()));
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// Reads the font file at `path`, making sure egui can use it before it is added.
+fn read_font(path: &Path) -> Result<FontData, String> {
+    let mut contents = Vec::new();
+    File::open(path)
+        .and_then(|file| BufReader::new(file).read_to_end(&mut contents))
+        .map_err(|err| format!("Failed to read `{}`: {}", path.display(), err))?;
+    egui_theme::validate_font(&contents, 0)
+        .map_err(|err| format!("`{}` cannot be used: {}", path.display(), err))?;
+    Ok(FontData::from_owned(contents))
 }
 
 fn add_font(
@@ -92,67 +279,818 @@ fn add_font(
             .add_enabled(enabled, Button::new("Add font to theme"))
             .clicked()
         {
-            use std::fs::File;
-            use std::io::BufReader;
-
-            if let Ok(file) = File::open(path) {
-                let mut reader = BufReader::new(file);
-                let mut contents = Vec::new();
-                if reader.read_to_end(&mut contents).is_ok() {
+            match read_font(path) {
+                Ok(data) => {
                     font_definitions
                         .font_data
-                        .insert(state.to_add_name.clone(), FontData::from_owned(contents));
+                        .insert(state.to_add_name.clone(), data);
+                    state.add_error = None;
                 }
-                // TODO: Give some sort of error popup or modal if this fails
+                Err(err) => state.add_error = Some(err),
             }
-            // TODO: Add an error message or modal if this fails.
+        }
+        if let Some(err) = &state.add_error {
+            ui.colored_label(Color32::RED, err);
         }
     });
     // fonts_updated
 }
 
+/// Reading every installed font takes a while, so it is done on another thread to keep the UI responsive.
+fn scan_system_fonts() -> Receiver<Vec<SystemFont>> {
+    let (sender, receiver) = mpsc::channel();
+    let scan = move || {
+        // The receiver is gone if the stylist was closed in the meantime.
+        let _ = sender.send(egui_theme::system_fonts());
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::spawn(scan);
+    // There are no threads on the web, and no font directories to read either.
+    #[cfg(target_arch = "wasm32")]
+    scan();
+    receiver
+}
+
+/// Lists the fonts installed on the host, so they can be previewed and added without looking up their files.
+fn system_fonts(state: &mut FontViewState, font_definitions: &mut FontDefinitions, ui: &mut Ui) {
+    if let Some(scan) = &state.system_font_scan {
+        match scan.try_recv() {
+            Ok(fonts) => {
+                state.system_fonts = Some(fonts);
+                state.system_font_scan = None;
+            }
+            Err(TryRecvError::Empty) => ui.ctx().request_repaint(),
+            Err(TryRecvError::Disconnected) => {
+                state.system_font_error = Some("Scanning the installed fonts failed".to_owned());
+                state.system_font_scan = None;
+            }
+        }
+    }
+    ui.horizontal(|ui| {
+        if state.system_font_scan.is_some() {
+            ui.label("Scanning...");
+        } else {
+            let scan_text = if state.system_fonts.is_some() {
+                "Rescan"
+            } else {
+                "Scan installed fonts"
+            };
+            if ui
+                .button(scan_text)
+                .on_hover_text("Reads every font in the system and fontconfig font directories")
+                .clicked()
+            {
+                state.system_font_scan = Some(scan_system_fonts());
+            }
+        }
+        TextEdit::singleline(&mut state.system_font_search)
+            .hint_text("Search by family name")
+            .ui(ui);
+    });
+    let fonts = match &state.system_fonts {
+        Some(fonts) => fonts,
+        None => {
+            if let Some(err) = &state.system_font_error {
+                ui.colored_label(Color32::RED, err);
+            }
+            return;
+        }
+    };
+    if fonts.is_empty() {
+        ui.label("No fonts were found");
+        return;
+    }
+
+    let search = state.system_font_search.to_lowercase();
+    let mut to_preview = None;
+    let mut to_add = None;
+    ScrollArea::vertical()
+        .id_source("_system_fonts")
+        .max_height(300.0)
+        .show(ui, |ui| {
+            Grid::new("_system_font_list")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    let matches = fonts
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, font)| font.family_name.to_lowercase().contains(&search));
+                    for (i, font) in matches {
+                        ui.label(&font.family_name)
+                            .on_hover_text(font.path.display().to_string());
+                        ui.label(font.style_name.as_deref().unwrap_or_default());
+                        ui.horizontal(|ui| {
+                            if ui.button("Preview").clicked() {
+                                to_preview = Some(i);
+                            }
+                            let name = font.name();
+                            let added = font_definitions.font_data.contains_key(&name);
+                            if ui
+                                .add_enabled(!added, Button::new("Add"))
+                                .on_disabled_hover_text(format!("`{name}` is already in the theme"))
+                                .clicked()
+                            {
+                                to_add = Some(i);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+
+    let error = |font: &SystemFont, err: FontError| {
+        format!("`{}` cannot be used: {}", font.path.display(), err)
+    };
+    if let Some(font) = to_preview.map(|i| &fonts[i]) {
+        match font.load() {
+            Ok(data) => {
+                state.system_font_preview = Some((font.to_owned(), data));
+                state.system_font_error = None;
+            }
+            Err(err) => state.system_font_error = Some(error(font, err)),
+        }
+    }
+    if let Some(font) = to_add.map(|i| &fonts[i]) {
+        match font.load() {
+            Ok(data) => {
+                font_definitions.font_data.insert(font.name(), data);
+                state.system_font_error = None;
+            }
+            Err(err) => state.system_font_error = Some(error(font, err)),
+        }
+    }
+    if let Some(err) = &state.system_font_error {
+        ui.colored_label(Color32::RED, err);
+    }
+    if let Some((font, _)) = &state.system_font_preview {
+        let samples = [(font.name(), preview_family(SYSTEM_PREVIEW_FONT))];
+        preview_samples("_system_font_preview", &samples, state, ui);
+    }
+}
+
+/// A font that is dragged onto the fallback chain of a family.
+struct DraggedFont {
+    name: String,
+    /// The family and position the font is dragged from, `None` when it is dragged from the installed fonts.
+    from: Option<(FontFamily, usize)>,
+}
+
+/// Makes `body` draggable, drawing it under the pointer while it is dragged.
+/// Returns the area of `body` and whether it is being dragged.
+fn drag_source(ui: &mut Ui, id: Id, body: impl FnOnce(&mut Ui)) -> (Rect, bool) {
+    if ui.memory().is_being_dragged(id) {
+        ui.output().cursor_icon = CursorIcon::Grabbing;
+        let layer_id = LayerId::new(Order::Tooltip, id);
+        let rect = ui.with_layer_id(layer_id, body).response.rect;
+        if let Some(pointer) = ui.ctx().pointer_interact_pos() {
+            ui.ctx().translate_layer(layer_id, pointer - rect.center());
+        }
+        (rect, true)
+    } else {
+        let rect = ui.scope(body).response.rect;
+        if ui.interact(rect, id, Sense::drag()).hovered() {
+            ui.output().cursor_icon = CursorIcon::Grab;
+        }
+        (rect, false)
+    }
+}
+
+/// The characters of `text` each font of the fallback chain `names` supplies, i.e. that no earlier font has a glyph for.
+/// Returns `None` until all fonts are loaded.
+fn supplied_chars(ui: &Ui, names: &[String], text: &str) -> Option<Vec<String>> {
+    if !names
+        .iter()
+        .all(|name| is_loaded(ui, &preview_family(name)))
+    {
+        return None;
+    }
+    let mut seen = HashSet::new();
+    let mut remaining = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && seen.insert(*c))
+        .collect::<Vec<_>>();
+    let fonts = ui.fonts();
+    let mut fonts = fonts.lock();
+    let supplied = names
+        .iter()
+        .map(|name| {
+            let characters = fonts
+                .fonts
+                .font(&FontId::new(16.0, preview_family(name)))
+                .characters();
+            let (supplied, rest) = remaining
+                .iter()
+                .partition::<Vec<char>, _>(|c| characters.contains(c));
+            remaining = rest;
+            supplied.into_iter().collect()
+        })
+        .collect();
+    Some(supplied)
+}
+
+/// Edits the fallback chain of `family`. Fonts are reordered by dragging them, and added by dragging them from the installed fonts.
 fn font_priority(
     id: &str,
     family: &FontFamily,
+    state: &mut FontViewState,
     font_definitions: &mut FontDefinitions,
     ui: &mut Ui,
 ) {
-    enum Direction {
-        Up,
-        Down,
+    let mut fonts = match font_definitions.families.get(family) {
+        Some(fonts) => fonts.clone(),
+        None => return,
+    };
+    if is_loaded(ui, family) {
+        let text = RichText::new(&state.preview_text).font(FontId::new(16.0, family.to_owned()));
+        ui.add(Label::new(text).wrap(true));
     }
-    // TODO: Implement this
-    let mut fonts = font_definitions
-        .families
-        .get(family)
-        .expect("this should be valid")
-        .clone();
-    let mut swap = None;
-
-    Grid::new(id).num_columns(3).show(ui, |ui| {
-        for (i, name) in fonts.iter().enumerate() {
-            ui.label(name);
-            if i > 0 {
-                if ui.button("increase").clicked() {
-                    swap = Some((Direction::Up, i));
+    let supplied = supplied_chars(ui, &fonts, &state.preview_text);
+    let mut rows = Vec::new();
+    let zone = ui
+        .vertical(|ui| {
+            Grid::new(id).num_columns(2).striped(true).show(ui, |ui| {
+                for (i, name) in fonts.iter().enumerate() {
+                    let drag_id = Id::new(id).with(i);
+                    let (rect, dragged) = drag_source(ui, drag_id, |ui| {
+                        ui.label(format!("☰ {name}"));
+                    });
+                    if dragged && state.dragged_font.is_none() {
+                        state.dragged_font = Some(DraggedFont {
+                            name: name.to_owned(),
+                            from: Some((family.to_owned(), i)),
+                        });
+                    }
+                    rows.push(rect);
+                    match supplied.as_ref().and_then(|supplied| supplied.get(i)) {
+                        Some(chars) if chars.is_empty() => {
+                            ui.weak("Not used for the preview text");
+                        }
+                        Some(chars) => {
+                            let font_id = FontId::new(16.0, preview_family(name));
+                            ui.add(Label::new(RichText::new(chars).font(font_id)).wrap(true));
+                        }
+                        None => loading_label(state, ui),
+                    }
+                    ui.end_row();
                 }
-            } else {
-                ui.label("");
+            });
+            if fonts.is_empty() {
+                ui.weak("Drag fonts here");
             }
-            if i < fonts.len() - 1 && ui.button("decrease").clicked() {
-                swap = Some((Direction::Down, i));
+        })
+        .response
+        .rect;
+
+    let dragged_font = match &state.dragged_font {
+        Some(dragged_font) => dragged_font,
+        None => return,
+    };
+    let pointer = match ui.ctx().pointer_interact_pos() {
+        Some(pointer) if zone.contains(pointer) => pointer,
+        _ => return,
+    };
+    let index = rows
+        .iter()
+        .filter(|rect| rect.center().y < pointer.y)
+        .count();
+    let y = rows.get(index).map_or(zone.bottom(), |rect| rect.top());
+    ui.painter().hline(
+        zone.x_range(),
+        y,
+        Stroke::new(2.0, ui.visuals().selection.stroke.color),
+    );
+    if !ui.input().pointer.any_released() {
+        return;
+    }
+    let existing = match &dragged_font.from {
+        Some((from, from_index)) if from == family => Some(*from_index),
+        // Dragging a font from another family or the installed fonts adds it, or moves it if it is already in the family.
+        _ => fonts.iter().position(|name| *name == dragged_font.name),
+    };
+    let from = existing.unwrap_or_else(|| {
+        fonts.push(dragged_font.name.clone());
+        fonts.len() - 1
+    });
+    egui_theme::move_font(&mut fonts, from, index);
+    font_definitions.families.insert(family.to_owned(), fonts);
+    state.dragged_font = None;
+}
+
+/// Checks which characters of the sample text and selected Unicode ranges each family cannot render.
+fn glyph_coverage(state: &mut FontViewState, font_definitions: &FontDefinitions, ui: &mut Ui) {
+    TextEdit::multiline(&mut state.coverage_text)
+        .hint_text("Sample text to check, e.g. translated UI strings")
+        .ui(ui);
+    CollapsingHeader::new("Unicode Ranges")
+        .id_source("_coverage_ranges")
+        .show(ui, |ui| {
+            for (range, checked) in UNICODE_RANGES.iter().zip(state.coverage_ranges.iter_mut()) {
+                Checkbox::new(checked, range.name).ui(ui);
             }
+        });
+    if ui.button("Check coverage").clicked() {
+        let ranges = UNICODE_RANGES
+            .iter()
+            .zip(state.coverage_ranges.iter())
+            .filter(|(_, checked)| **checked)
+            .flat_map(|(range, _)| range.chars());
+        let chars = state.coverage_text.chars().chain(ranges);
+        state.missing_glyphs = Some(egui_theme::missing_glyphs(font_definitions, chars));
+    }
+    if let Some(missing_glyphs) = &state.missing_glyphs {
+        Grid::new("_glyph_coverage")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (family, missing) in missing_glyphs.iter() {
+                    ui.label(family.to_string());
+                    if missing.is_empty() {
+                        ui.label("All glyphs covered");
+                        ui.label("");
+                    } else {
+                        ui.colored_label(Color32::RED, format!("{} missing", missing.len()));
+                        // Very large ranges would make the grid unusable, show the full list on hover.
+                        let preview = missing.iter().take(32).collect::<String>();
+                        let all = missing.iter().collect::<String>();
+                        ui.add(Label::new(preview).wrap(true)).on_hover_ui(|ui| {
+                            ui.add(Label::new(all).wrap(true));
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// The definitions being edited plus a family for each font, so that the preview panel can draw every font on its own.
+/// The previewed system font is added as well.
+pub(crate) fn preview_font_definitions(
+    font_definitions: &FontDefinitions,
+    state: &FontViewState,
+) -> FontDefinitions {
+    let mut preview = font_definitions.clone();
+    if let Some((_, data)) = &state.system_font_preview {
+        preview
+            .font_data
+            .insert(SYSTEM_PREVIEW_FONT.to_owned(), data.clone());
+    }
+    for name in preview.font_data.keys() {
+        preview
+            .families
+            .insert(preview_family(name), vec![name.to_owned()]);
+    }
+    preview
+}
+
+/// Identifies the fonts `preview_font_definitions` builds without comparing their data, so they are only rebuilt when
+/// something changed. Fonts are told apart by the address, length and face index of their data, like `CachedFontInfo`.
+#[derive(PartialEq)]
+pub(crate) struct PreviewFontsKey {
+    font_data: Vec<(String, (usize, usize, u32), FontTweak)>,
+    families: BTreeMap<FontFamily, Vec<String>>,
+    system_font: Option<(usize, usize, u32)>,
+}
+
+pub(crate) fn preview_fonts_key(
+    font_definitions: &FontDefinitions,
+    state: &FontViewState,
+) -> PreviewFontsKey {
+    let data_key = |data: &FontData| (data.font.as_ptr() as usize, data.font.len(), data.index);
+    PreviewFontsKey {
+        font_data: font_definitions
+            .font_data
+            .iter()
+            .map(|(name, data)| (name.to_owned(), data_key(data), data.tweak))
+            .collect(),
+        families: font_definitions.families.clone(),
+        system_font: state
+            .system_font_preview
+            .as_ref()
+            .map(|(_, data)| data_key(data)),
+    }
+}
+
+/// The family that only contains the font `name`.
+fn preview_family(name: &str) -> FontFamily {
+    FontFamily::Name(format!("{PREVIEW_FAMILY_PREFIX}{name}").into())
+}
+
+/// Fonts set with `Context::set_fonts` are only used from the next frame on, and egui panics when drawing with a family or font it doesn't know yet.
+fn is_loaded(ui: &Ui, family: &FontFamily) -> bool {
+    let fonts = ui.fonts();
+    let fonts = fonts.lock();
+    let definitions = fonts.fonts.definitions();
+    definitions.families.get(family).is_some_and(|names| {
+        names
+            .iter()
+            .all(|name| definitions.font_data.contains_key(name))
+    })
+}
+
+/// Shown in place of a font that is not loaded yet.
+fn loading_label(state: &FontViewState, ui: &mut Ui) {
+    if state.preview_enabled {
+        ui.label("Loading...");
+    } else {
+        ui.weak("Turn on the preview to see the fonts");
+    }
+}
+
+fn preview_samples(id: &str, samples: &[(String, FontFamily)], state: &FontViewState, ui: &mut Ui) {
+    Grid::new(id).num_columns(2).striped(true).show(ui, |ui| {
+        for (label, family) in samples {
+            ui.label(label);
+            ui.vertical(|ui| {
+                if !is_loaded(ui, family) {
+                    loading_label(state, ui);
+                    return;
+                }
+                for size in state.preview_sizes.iter() {
+                    let text = RichText::new(&state.preview_text)
+                        .font(FontId::new(*size, family.to_owned()));
+                    ui.add(Label::new(text).wrap(true));
+                }
+            });
             ui.end_row();
         }
     });
-    if let Some((dir, index)) = swap {
-        let new_index = match dir {
-            Direction::Up => index - 1,
-            Direction::Down => index + 1,
+}
+
+/// Draws the sample text in every installed font and every family, using the font definitions being edited.
+fn font_preview(state: &mut FontViewState, font_definitions: &FontDefinitions, ui: &mut Ui) {
+    TextEdit::multiline(&mut state.preview_text)
+        .hint_text("Sample text to preview")
+        .ui(ui);
+    ui.horizontal(|ui| {
+        ui.label("Sizes");
+        let mut to_remove = None;
+        let removable = state.preview_sizes.len() > 1;
+        for (i, size) in state.preview_sizes.iter_mut().enumerate() {
+            DragValue::new(size).clamp_range(4.0f32..=128.0f32).ui(ui);
+            if removable && ui.small_button("x").clicked() {
+                to_remove = Some(i);
+            }
+        }
+        if let Some(i) = to_remove {
+            state.preview_sizes.remove(i);
+        }
+        if ui.small_button("+").clicked() {
+            let size = state.preview_sizes.last().map_or(16.0, |size| size * 1.5);
+            state.preview_sizes.push(size.min(128.0));
+        }
+    });
+    CollapsingHeader::new("Fonts")
+        .id_source("_preview_fonts")
+        .default_open(true)
+        .show(ui, |ui| {
+            let samples = font_definitions
+                .font_data
+                .keys()
+                .map(|name| (name.to_owned(), preview_family(name)))
+                .collect::<Vec<_>>();
+            preview_samples("_preview_font_samples", &samples, state, ui);
+        });
+    CollapsingHeader::new("Families")
+        .id_source("_preview_families")
+        .default_open(true)
+        .show(ui, |ui| {
+            let samples = font_definitions
+                .families
+                .keys()
+                .map(|family| (family.to_string(), family.to_owned()))
+                .collect::<Vec<_>>();
+            preview_samples("_preview_family_samples", &samples, state, ui);
+        });
+}
+
+/// A few characters of the sample text the font has glyphs for, or the first characters of the font, e.g. for icon fonts.
+fn baseline_sample(ui: &Ui, name: &str, text: &str, size: f32) -> String {
+    let fonts = ui.fonts();
+    let mut fonts = fonts.lock();
+    let characters = fonts
+        .fonts
+        .font(&FontId::new(size, preview_family(name)))
+        .characters();
+    let sample = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && characters.contains(c))
+        .take(6)
+        .collect::<String>();
+    if sample.is_empty() {
+        characters
+            .iter()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .take(4)
+            .collect()
+    } else {
+        sample
+    }
+}
+
+/// Draws a sample of every font of `family` in a single row, as egui lays out mixed text, with a line on the baseline of the first font.
+fn baseline_preview(
+    state: &FontViewState,
+    family: &FontFamily,
+    font_definitions: &FontDefinitions,
+    ui: &mut Ui,
+) {
+    let names = &font_definitions.families[family];
+    if names.is_empty() {
+        ui.label("No fonts");
+        return;
+    }
+    if !names
+        .iter()
+        .all(|name| is_loaded(ui, &preview_family(name)))
+    {
+        loading_label(state, ui);
+        return;
+    }
+    let size = state.preview_sizes.iter().copied().fold(16.0f32, f32::max);
+    let mut job = LayoutJob::default();
+    for name in names {
+        let sample = baseline_sample(ui, name, &state.preview_text, size);
+        let color = if state.tweak_font.as_deref() == Some(name.as_str()) {
+            ui.visuals().strong_text_color()
+        } else {
+            ui.visuals().text_color()
         };
-        let font = fonts.remove(index);
-        fonts.insert(new_index, font);
-        font_definitions.families.insert(family.to_owned(), fonts);
+        let format = TextFormat::simple(FontId::new(size, preview_family(name)), color);
+        job.append(&format!("{sample} "), 0.0, format);
+    }
+    let galley = ui.fonts().layout_job(job);
+    let (rect, _) = ui.allocate_exact_size(galley.size(), Sense::hover());
+    // Upper case letters and digits sit on the baseline, fall back to the first glyph of the first font.
+    let baseline = galley.rows.first().and_then(|row| {
+        let glyphs = row.glyphs.iter().filter(|glyph| glyph.section_index == 0);
+        glyphs
+            .clone()
+            .find(|glyph| glyph.chr.is_uppercase() || glyph.chr.is_ascii_digit())
+            .or_else(|| glyphs.clone().next())
+            .map(|glyph| glyph.pos.y + glyph.uv_rect.offset.y + glyph.uv_rect.size.y)
+    });
+    ui.painter().galley(rect.min, galley);
+    if let Some(baseline) = baseline {
+        ui.painter().hline(
+            rect.x_range(),
+            rect.min.y + baseline,
+            Stroke::new(1.0, Color32::RED),
+        );
+    }
+}
+
+/// Edits the scale and vertical offset of a font, previewed against the other fonts of every family it is in.
+fn font_tweaks(state: &mut FontViewState, font_definitions: &mut FontDefinitions, ui: &mut Ui) {
+    if let Some(name) = &state.tweak_font {
+        if !font_definitions.font_data.contains_key(name) {
+            state.tweak_font = None;
+        }
+    }
+    ComboBox::from_id_source("_tweak_font")
+        .selected_text(state.tweak_font.as_deref().unwrap_or("Select a font"))
+        .show_ui(ui, |ui| {
+            for name in font_definitions.font_data.keys() {
+                ui.selectable_value(&mut state.tweak_font, Some(name.to_owned()), name);
+            }
+        });
+    let name = match state.tweak_font.clone() {
+        Some(name) => name,
+        None => return,
+    };
+    let data = font_definitions
+        .font_data
+        .get_mut(&name)
+        .expect("the font exists");
+    Grid::new("_font_tweak").num_columns(2).show(ui, |ui| {
+        ui.label("Scale");
+        DragValue::new(&mut data.tweak.scale)
+            .clamp_range(0.1f32..=4.0f32)
+            .speed(0.01)
+            .ui(ui);
+        ui.end_row();
+        ui.label("Y Offset Factor")
+            .on_hover_text("Shifts the font down by this fraction of the font size");
+        DragValue::new(&mut data.tweak.y_offset_factor)
+            .clamp_range(-1.0f32..=1.0f32)
+            .speed(0.01)
+            .ui(ui);
+        ui.end_row();
+        ui.label("Y Offset")
+            .on_hover_text("Shifts the font down by this many points");
+        DragValue::new(&mut data.tweak.y_offset)
+            .clamp_range(-32.0f32..=32.0f32)
+            .speed(0.1)
+            .ui(ui);
+        ui.end_row();
+    });
+    if ui.button("Reset").clicked() {
+        // Some of the built-in fonts come with their own tweak.
+        data.tweak = egui_theme::builtin_font(&name)
+            .map_or_else(FontTweak::default, |builtin| builtin.tweak);
+    }
+
+    let families = font_definitions
+        .families
+        .iter()
+        .filter(|(_, names)| names.contains(&name))
+        .map(|(family, _)| family.to_owned())
+        .collect::<Vec<_>>();
+    if families.is_empty() {
+        ui.label("The font is not used by any family.");
+    }
+    for family in families {
+        ui.label(family.to_string());
+        baseline_preview(state, &family, font_definitions, ui);
+    }
+}
+
+/// Renames fonts and families, keeping them in their families and text styles.
+fn rename(
+    state: &mut FontViewState,
+    font_definitions: &mut FontDefinitions,
+    style: &mut Style,
+    ui: &mut Ui,
+) {
+    Grid::new("_rename").num_columns(4).show(ui, |ui| {
+        ui.label("Font");
+        ComboBox::from_id_source("_rename_font")
+            .selected_text(state.rename_font.as_deref().unwrap_or("Select a font"))
+            .show_ui(ui, |ui| {
+                let fonts = font_definitions
+                    .font_data
+                    .keys()
+                    .filter(|name| !egui_theme::is_builtin_font(name));
+                for name in fonts {
+                    ui.selectable_value(&mut state.rename_font, Some(name.to_owned()), name);
+                }
+            });
+        TextEdit::singleline(&mut state.rename_font_to)
+            .hint_text("New name")
+            .ui(ui);
+        let enabled = state.rename_font.is_some() && !state.rename_font_to.is_empty();
+        if ui.add_enabled(enabled, Button::new("Rename")).clicked() {
+            if let Some(from) = state.rename_font.clone() {
+                let to = state.rename_font_to.clone();
+                state.rename_error = match egui_theme::rename_font(font_definitions, &from, &to) {
+                    Ok(()) => {
+                        state.font_renamed(&from, &to);
+                        state.rename_font = None;
+                        state.rename_font_to.clear();
+                        None
+                    }
+                    Err(err) => Some(err.to_string()),
+                };
+            }
+        }
+        ui.end_row();
+
+        ui.label("Family");
+        ComboBox::from_id_source("_rename_family")
+            .selected_text(state.rename_family.as_deref().unwrap_or("Select a family"))
+            .show_ui(ui, |ui| {
+                for family in font_definitions.families.keys() {
+                    if let FontFamily::Name(name) = family {
+                        ui.selectable_value(
+                            &mut state.rename_family,
+                            Some(name.to_string()),
+                            name.as_ref(),
+                        );
+                    }
+                }
+            });
+        TextEdit::singleline(&mut state.rename_family_to)
+            .hint_text("New name")
+            .ui(ui);
+        let enabled = state.rename_family.is_some() && !state.rename_family_to.is_empty();
+        if ui.add_enabled(enabled, Button::new("Rename")).clicked() {
+            if let Some(from) = state.rename_family.clone() {
+                let to = state.rename_family_to.clone();
+                state.rename_error =
+                    match egui_theme::rename_family(font_definitions, style, &from, &to) {
+                        Ok(()) => {
+                            state.family_renamed(
+                                &FontFamily::Name(from.into()),
+                                &FontFamily::Name(to.into()),
+                            );
+                            state.rename_family = None;
+                            state.rename_family_to.clear();
+                            None
+                        }
+                        Err(err) => Some(err.to_string()),
+                    };
+            }
+        }
+        ui.end_row();
+    });
+    if let Some(err) = &state.rename_error {
+        ui.colored_label(Color32::RED, err);
+    }
+}
+
+fn is_family_used(style: &Style, family: &FontFamily) -> bool {
+    !egui_theme::text_styles_using_family(style, family).is_empty()
+        || style
+            .override_font_id
+            .as_ref()
+            .is_some_and(|font_id| font_id.family == *family)
+}
+
+/// Asks before deleting a font that is still listed by a family.
+fn confirm_font_delete(
+    state: &mut FontViewState,
+    font_definitions: &mut FontDefinitions,
+    ui: &mut Ui,
+) {
+    let name = match state.pending_font_delete.clone() {
+        Some(name) => name,
+        None => return,
+    };
+    let families = egui_theme::families_using_font(font_definitions, &name)
+        .iter()
+        .map(|family| family.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    ui.colored_label(
+        Color32::YELLOW,
+        format!("`{name}` is used by {families} and will be removed from them."),
+    );
+    ui.horizontal(|ui| {
+        if ui.button("Delete").clicked() {
+            egui_theme::remove_font(font_definitions, &name);
+            state.pending_font_delete = None;
+        }
+        if ui.button("Cancel").clicked() {
+            state.pending_font_delete = None;
+        }
+    });
+}
+
+/// Asks before deleting a family that is still used by text styles, and which family they should use instead.
+fn confirm_family_delete(
+    state: &mut FontViewState,
+    font_definitions: &mut FontDefinitions,
+    style: &mut Style,
+    ui: &mut Ui,
+) {
+    let family = match state.pending_family_delete.clone() {
+        Some(family) => family,
+        None => return,
+    };
+    let mut users = egui_theme::text_styles_using_family(style, &family)
+        .iter()
+        .map(|text_style| text_style.to_string())
+        .collect::<Vec<_>>();
+    if is_family_used(style, &family) && users.is_empty() {
+        users.push("the override font".to_owned());
+    }
+    ui.colored_label(
+        Color32::YELLOW,
+        format!("`{family}` is used by {}.", users.join(", ")),
+    );
+    if state.replacement_family == family {
+        state.replacement_family = FontFamily::Proportional;
+    }
+    ui.horizontal(|ui| {
+        ui.label("Move them to");
+        ComboBox::from_id_source("_replacement_family")
+            .selected_text(state.replacement_family.to_string())
+            .show_ui(ui, |ui| {
+                for other in font_definitions
+                    .families
+                    .keys()
+                    .filter(|other| **other != family)
+                {
+                    ui.selectable_value(
+                        &mut state.replacement_family,
+                        other.to_owned(),
+                        other.to_string(),
+                    );
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        if ui.button("Delete").clicked() {
+            match egui_theme::remove_family(
+                font_definitions,
+                style,
+                &family,
+                &state.replacement_family,
+            ) {
+                Ok(_) => {
+                    state.pending_family_delete = None;
+                    state.family_delete_error = None;
+                }
+                Err(err) => state.family_delete_error = Some(err.to_string()),
+            }
+        }
+        if ui.button("Cancel").clicked() {
+            state.pending_family_delete = None;
+            state.family_delete_error = None;
+        }
+    });
+    if let Some(err) = &state.family_delete_error {
+        ui.colored_label(Color32::RED, err);
     }
 }
 
@@ -168,6 +1106,35 @@ pub fn fonts_view(
     // let mut fonts_updated = false;
     // font_definitions.
     ui.heading("Fonts Menu");
+    let dangling = egui_theme::dangling_references(font_definitions, style);
+    if !dangling.is_empty() {
+        CollapsingHeader::new("Dangling References")
+            .default_open(true)
+            .show(ui, |ui| {
+                for reference in dangling.iter() {
+                    ui.colored_label(Color32::RED, reference.to_string());
+                }
+                if ui
+                    .button("Fix")
+                    .on_hover_text("Removes unknown fonts from the families and moves text styles using unknown families to Proportional")
+                    .clicked()
+                {
+                    egui_theme::fix_dangling_references(font_definitions, style);
+                }
+            });
+    }
+    if !state.load_issues.is_empty() {
+        CollapsingHeader::new("Theme Load Issues")
+            .default_open(true)
+            .show(ui, |ui| {
+                for issue in state.load_issues.iter() {
+                    ui.colored_label(Color32::RED, issue.to_string());
+                }
+                if ui.button("Dismiss").clicked() {
+                    state.load_issues.clear();
+                }
+            });
+    }
     // This is a workaround for the default fonts which will crash the interface if they are deleted.
     CollapsingHeader::new("General Settings")
         .default_open(true)
@@ -244,27 +1211,72 @@ pub fn fonts_view(
     CollapsingHeader::new("Installed Fonts")
         .default_open(true)
         .show(ui, |ui| {
-            Grid::new("_fonts").num_columns(3).show(ui, |ui| {
-                for (name, _data) in font_definitions.font_data.iter() {
-                    ui.label(name);
-                    // Cannot delete the default egui fonts without breaking things.
-                    if !DEFAULT_FONTS.contains(&name.as_str()) && ui.button("Delete").clicked() {
-                        state.to_delete.push(name.to_owned());
-                    }
+            state.prune_font_info(font_definitions);
+            Grid::new("_fonts")
+                .num_columns(7)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Name");
+                    ui.strong("Family");
+                    ui.strong("Style");
+                    ui.strong("Glyphs");
+                    ui.strong("Size");
+                    ui.strong("Scripts");
                     ui.end_row();
-                }
+                    for (name, data) in font_definitions.font_data.iter() {
+                        // Installed fonts can be dragged onto the font priority of a family.
+                        let drag_id = Id::new("_installed_font").with(name);
+                        let (_, dragged) = drag_source(ui, drag_id, |ui| {
+                            ui.label(name);
+                        });
+                        if dragged && state.dragged_font.is_none() {
+                            state.dragged_font = Some(DraggedFont {
+                                name: name.to_owned(),
+                                from: None,
+                            });
+                        }
+                        match state.font_info(name, data) {
+                            Ok(info) => font_info_row(info, ui),
+                            Err(err) => {
+                                ui.colored_label(Color32::RED, err.to_string());
+                                for _ in 0..4 {
+                                    ui.label("");
+                                }
+                            }
+                        }
+                        // Cannot delete the default egui fonts without breaking things.
+                        if !egui_theme::is_builtin_font(name) && ui.button("Delete").clicked() {
+                            if egui_theme::families_using_font(font_definitions, name).is_empty() {
+                                state.to_delete.push(name.to_owned());
+                            } else {
+                                state.pending_font_delete = Some(name.to_owned());
+                            }
+                        }
+                        ui.end_row();
+                    }
 
-                for key in state.to_delete.iter() {
-                    font_definitions.font_data.remove(key);
-                }
-                state.to_delete.clear();
-            });
+                    for key in state.to_delete.iter() {
+                        egui_theme::remove_font(font_definitions, key);
+                    }
+                    state.to_delete.clear();
+                });
+            confirm_font_delete(state, font_definitions, ui);
+            if ui
+                .button("Restore default fonts")
+                .on_hover_text("Adds the fonts bundled with egui back to the default families")
+                .clicked()
+            {
+                egui_theme::restore_default_fonts(font_definitions);
+            }
         });
     CollapsingHeader::new("Add font")
         .default_open(true)
         .show(ui, |ui| {
             add_font(state, font_definitions, file_dialog_callback, ui)
         });
+    CollapsingHeader::new("System Fonts")
+        .default_open(false)
+        .show(ui, |ui| system_fonts(state, font_definitions, ui));
     CollapsingHeader::new("Add/Remove Font Families")
         .default_open(true)
         .show(ui, |ui| {
@@ -279,7 +1291,12 @@ pub fn fonts_view(
                             _ => {
                                 let response = ui.button("delete");
                                 if response.clicked() {
-                                    to_delete.push(family.to_owned());
+                                    if is_family_used(style, family) {
+                                        state.pending_family_delete = Some(family.to_owned());
+                                        state.family_delete_error = None;
+                                    } else {
+                                        to_delete.push(family.to_owned());
+                                    }
                                 }
                             }
                         }
@@ -300,8 +1317,12 @@ pub fn fonts_view(
                         font_definitions.families.remove(&key);
                     }
                 });
+            confirm_family_delete(state, font_definitions, style, ui);
         });
 
+    CollapsingHeader::new("Rename Fonts and Families")
+        .default_open(false)
+        .show(ui, |ui| rename(state, font_definitions, style, ui));
     CollapsingHeader::new("Edit Font Families")
         .default_open(true)
         .show(ui, |ui| {
@@ -312,8 +1333,8 @@ pub fn fonts_view(
                     ui.label("Font");
                     let map = font_definitions
                         .families
-                        .iter()
-                        .map(|(k, _)| k.to_string())
+                        .keys()
+                        .map(|k| k.to_string())
                         .collect::<Vec<String>>();
                     for entry in map {
                         ui.label(entry);
@@ -349,17 +1370,29 @@ pub fn fonts_view(
         .show(ui, |ui| {
             let families = font_definitions
                 .families
-                .iter()
-                .map(|(k, _)| k.clone())
+                .keys()
+                .cloned()
                 .collect::<Vec<FontFamily>>();
             for family in families.iter() {
                 CollapsingHeader::new(format!("{family} Priority").as_str())
                     .default_open(true)
                     .show(ui, |ui| {
                         let id = format!("_{family}_priority");
-                        font_priority(id.as_str(), family, font_definitions, ui);
-                        ui.end_row();
+                        font_priority(id.as_str(), family, state, font_definitions, ui);
                     });
             }
         });
+    CollapsingHeader::new("Font Tweaks")
+        .default_open(false)
+        .show(ui, |ui| font_tweaks(state, font_definitions, ui));
+    CollapsingHeader::new("Font Preview")
+        .default_open(false)
+        .show(ui, |ui| font_preview(state, font_definitions, ui));
+    CollapsingHeader::new("Glyph Coverage")
+        .default_open(false)
+        .show(ui, |ui| glyph_coverage(state, font_definitions, ui));
+    // A font that was dropped outside of every family is discarded.
+    if !ui.memory().is_anything_being_dragged() {
+        state.dragged_font = None;
+    }
 }
@@ -2,7 +2,7 @@
 use std::path::PathBuf;
 
 use egui::{CentralPanel, Checkbox, FontDefinitions, ScrollArea, SidePanel, Style, Ui, Widget};
-use egui_theme::EguiTheme;
+use egui_theme::{EguiTheme, ThemeError, ThemeSection};
 use serde::{Deserialize, Serialize};
 mod colors;
 mod fonts;
@@ -13,7 +13,7 @@ mod text;
 
 use preview::Preview;
 
-use fonts::FontViewState;
+use fonts::{FontViewState, PreviewFontsKey};
 use text::TextStyleViewState;
 
 /// StylistFileDialogFunction is a function callback that allows the `StylistState` to open a native filedialog and get file paths for egui.
@@ -54,13 +54,19 @@ pub struct StylistState {
     font_view_state: FontViewState,
     #[serde(skip)]
     text_style_view_state: TextStyleViewState,
+    /// The fonts the preview last gave the context, so they are only set again when they change.
+    #[serde(skip)]
+    preview_fonts: Option<PreviewFontsKey>,
+    /// The fonts of the host application, restored when the preview is turned off.
+    #[serde(skip)]
+    host_fonts: Option<FontDefinitions>,
     preview: Preview,
     #[serde(skip)]
     pub file_dialog_function: Option<StylistFileDialogFunction>,
 }
 
-impl StylistState {
-    pub fn default() -> Self {
+impl Default for StylistState {
+    fn default() -> Self {
         Self {
             current_tab: StylerTab::Colors,
             style: Style::default(),
@@ -69,10 +75,15 @@ impl StylistState {
             font_definitions: FontDefinitions::default(),
             font_view_state: FontViewState::default(),
             text_style_view_state: TextStyleViewState::default(),
+            preview_fonts: None,
+            host_fonts: None,
             preview: Preview::new(Style::default()),
             file_dialog_function: None,
         }
     }
+}
+
+impl StylistState {
     /// Sets `file_dialog_function` with the function call that it can use to
     pub fn set_file_dialog_function(&mut self, f: StylistFileDialogFunction) {
         self.file_dialog_function = Some(f);
@@ -148,6 +159,7 @@ impl StylistState {
     pub fn ui(&mut self, ui: &mut Ui) {
         // Get the tab ui
         self.tab_menu_ui(ui);
+        self.update_preview_fonts(ui);
         if self.show_stylist {
             SidePanel::left("_stylist_panel")
                 .width_range(300.0..=900.0)
@@ -187,18 +199,54 @@ impl StylistState {
             CentralPanel::default().show_inside(ui, |ui| {
                 ScrollArea::vertical().show(ui, |ui| {
                     self.preview.set_style(self.style.clone());
-                    ui.ctx().set_fonts(self.font_definitions.clone());
                     self.preview.show(ui);
                 });
             });
         }
     }
+    /// Gives the context the fonts being edited while the preview is shown, and the host fonts back once it is hidden.
+    /// Building the preview fonts clones every font, so that only happens when they changed.
+    fn update_preview_fonts(&mut self, ui: &Ui) {
+        self.font_view_state.preview_enabled = self.show_preview;
+        if !self.show_preview {
+            if let Some(host_fonts) = self.host_fonts.take() {
+                ui.ctx().set_fonts(host_fonts);
+            }
+            self.preview_fonts = None;
+            return;
+        }
+        let key = fonts::preview_fonts_key(&self.font_definitions, &self.font_view_state);
+        if self.preview_fonts.as_ref() == Some(&key) {
+            return;
+        }
+        if self.host_fonts.is_none() {
+            self.host_fonts = Some(ui.fonts().lock().fonts.definitions().clone());
+        }
+        ui.ctx().set_fonts(fonts::preview_font_definitions(
+            &self.font_definitions,
+            &self.font_view_state,
+        ));
+        self.preview_fonts = Some(key);
+    }
     pub fn export_theme(&self) -> EguiTheme {
         EguiTheme::new(self.style.clone(), self.font_definitions.clone())
     }
+    /// Exports the theme with every embedded font reduced to the glyphs needed for `chars`.
+    pub fn export_subset_theme(
+        &self,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<EguiTheme, ThemeError> {
+        let font_definitions = egui_theme::subset_fonts(self.font_definitions.clone(), chars)?;
+        Ok(EguiTheme::new(self.style.clone(), font_definitions))
+    }
+    /// Replaces the current style and fonts with the theme. Any font issues found while loading are shown in the fonts tab.
     pub fn import_theme(&mut self, theme: EguiTheme) {
-        let (style, font_definitions) = theme.extract();
+        let (style, font_definitions, issues) = theme.extract_with_report();
         self.style = style;
         self.font_definitions = font_definitions;
+        self.font_view_state.load_issues = issues
+            .into_iter()
+            .filter(|issue| issue.section == ThemeSection::Fonts)
+            .collect();
     }
 }
@@ -3,15 +3,21 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod theme;
-pub use theme::EguiTheme;
+#[cfg(feature = "binary")]
+pub use theme::ThemeCompression;
+pub use theme::{
+    builtin_font, builtin_font_names, dangling_references, diff, diff_themes, families_using_font,
+    fix_dangling_references, is_builtin_font, merge_themes, missing_glyphs, move_font,
+    remove_family, remove_font, rename_family, rename_font, restore_default_fonts, scan_font_dirs,
+    system_font_dirs, system_fonts, text_styles_using_family, validate_font, Compatibility,
+    DanglingReference, EguiTheme, FontError, FontInfo, FontReference, FontResolver, FontSource,
+    Migration, MigrationFn, MigrationRegistry, RangeCoverage, SystemFont, ThemeBase, ThemeChange,
+    ThemeConflict, ThemeDiff, ThemeError, ThemeFormat, ThemeIssue, ThemeIssueKind, ThemeMerge,
+    ThemeResolver, ThemeSection, UnicodeRange, UNICODE_RANGES,
+};
+#[cfg(feature = "subset")]
+pub use theme::{latin1_chars, subset_font, subset_fonts};
 #[cfg(test)]
 mod tests;
 
 include!(concat!(env!("OUT_DIR"), "/generated/meta.rs"));
-
-const DEFAULT_FONTS: [&str; 4] = [
-    "Hack",
-    "Ubuntu-Light",
-    "NotoEmoji-Regular",
-    "emoji-icon-font",
-];
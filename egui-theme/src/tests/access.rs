@@ -0,0 +1,128 @@
+use egui::{Color32, FontDefinitions, Style};
+
+use crate::{EguiTheme, ThemeBase, ThemeError};
+
+#[test]
+fn test_get_set() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    assert_eq!(
+        theme.get::<Color32>("visuals.hyperlink_color"),
+        Ok(Style::default().visuals.hyperlink_color),
+        "hyperlink_color should decode"
+    );
+    theme
+        .set("visuals.hyperlink_color", Color32::RED)
+        .expect("set should succeed");
+    theme
+        .set("visuals.widgets.hovered.bg_fill", Color32::GREEN)
+        .expect("set should succeed");
+    assert_eq!(
+        theme.get::<Color32>("visuals.widgets.hovered.bg_fill"),
+        Ok(Color32::GREEN),
+        "nested fields should decode"
+    );
+
+    let (style, _) = theme
+        .extract_strict()
+        .expect("the theme should still be valid");
+    assert_eq!(
+        style.visuals.hyperlink_color,
+        Color32::RED,
+        "color should match"
+    );
+    assert_eq!(
+        style.visuals.widgets.hovered.bg_fill,
+        Color32::GREEN,
+        "nested color should match"
+    );
+}
+
+#[test]
+fn test_get_errors() {
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    assert_eq!(
+        theme.get::<Color32>("visuals.not_a_color"),
+        Err(ThemeError::MissingKey("visuals.not_a_color".to_owned())),
+        "unknown keys should be missing"
+    );
+    assert!(
+        matches!(
+            theme.get::<Color32>("visuals.dark_mode"),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "visuals.dark_mode"
+        ),
+        "a bool should not decode as a color"
+    );
+}
+
+#[test]
+fn test_set_errors() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    assert_eq!(
+        theme.get::<Option<Color32>>("visuals.override_text_color"),
+        Ok(None)
+    );
+    assert!(
+        matches!(
+            theme.set("visuals.override_text_color.r", 5u8),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "visuals.override_text_color.r"
+        ),
+        "fields cannot be set inside a null value"
+    );
+    assert!(
+        matches!(
+            theme.set("visuals.dark_mode.value", true),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "visuals.dark_mode.value"
+        ),
+        "fields cannot be set inside a bool"
+    );
+    theme
+        .extract_strict()
+        .expect("failed sets should leave the theme untouched");
+}
+
+#[test]
+fn test_keys_and_remove() {
+    let mut theme = EguiTheme::new_with_base(
+        Style::default(),
+        FontDefinitions::default(),
+        ThemeBase::Dark,
+        &|_: &str| unreachable!(),
+    )
+    .expect("built-in bases should always resolve");
+    assert!(theme.keys().is_empty(), "no keys should be stored");
+
+    theme
+        .set("visuals.widgets.active.bg_fill", Color32::BLUE)
+        .expect("set should succeed on keys that are not stored yet");
+    theme
+        .set("spacing.slider_width", 42.0)
+        .expect("set should succeed");
+    assert_eq!(
+        theme.keys(),
+        vec!["spacing.slider_width", "visuals.widgets"],
+        "keys should be sorted"
+    );
+    assert!(
+        theme.contains_key("visuals.widgets.active.bg_fill"),
+        "nested key should exist"
+    );
+
+    assert_eq!(
+        theme.remove("spacing.slider_width"),
+        Some(serde_json::json!(42.0)),
+        "the removed value should be returned"
+    );
+    let (style, _) = theme
+        .extract_strict()
+        .expect("the theme should still be valid");
+    assert_eq!(
+        style.spacing.slider_width,
+        Style::default().spacing.slider_width,
+        "removed keys should be inherited from the base"
+    );
+    assert_eq!(
+        style.visuals.widgets.active.bg_fill,
+        Color32::BLUE,
+        "nested color should match"
+    );
+}
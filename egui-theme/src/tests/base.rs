@@ -0,0 +1,158 @@
+use egui::{Color32, FontDefinitions, Style, Visuals};
+
+use super::nacelle_fonts;
+use crate::{EguiTheme, ThemeBase, ThemeError, ThemeIssueKind};
+
+fn no_themes(name: &str) -> Result<EguiTheme, ThemeError> {
+    panic!("`{name}` should not need to be resolved")
+}
+
+fn light_style() -> Style {
+    Style {
+        visuals: Visuals::light(),
+        ..Style::default()
+    }
+}
+
+#[test]
+fn test_builtin_base() {
+    let mut style = light_style();
+    style.visuals.hyperlink_color = Color32::RED;
+    let theme = EguiTheme::new_with_base(
+        style.clone(),
+        FontDefinitions::default(),
+        ThemeBase::Light,
+        &no_themes,
+    )
+    .expect("built-in bases should always resolve");
+    assert_eq!(theme.base(), Some(&ThemeBase::Light), "base should be set");
+    assert_eq!(
+        theme.style.keys().collect::<Vec<_>>(),
+        vec!["visuals.hyperlink_color"],
+        "only the overridden key should be stored"
+    );
+    assert!(theme.fonts.is_empty(), "fonts should be inherited");
+
+    let serialized = ron::to_string(&theme).expect("serialization failed");
+    let deserialized = ron::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+    let (de_style, de_fonts) = deserialized
+        .extract_strict()
+        .expect("built-in bases should be merged when extracting");
+    assert_eq!(de_style, style, "style should match");
+    assert_eq!(de_fonts, FontDefinitions::default(), "fonts should match");
+}
+
+#[test]
+fn test_theme_base() {
+    let mut brand_style = Style::default();
+    brand_style.visuals.faint_bg_color = Color32::BLUE;
+    let brand = || {
+        EguiTheme::new_with_base(
+            brand_style.clone(),
+            FontDefinitions::default(),
+            ThemeBase::Dark,
+            &no_themes,
+        )
+    };
+    let resolver = |name: &str| {
+        assert_eq!(name, "brand.eguitheme", "unexpected base theme");
+        brand()
+    };
+
+    let mut customer_style = brand_style.clone();
+    customer_style.spacing.slider_width = 42.0;
+    let theme = EguiTheme::new_with_base(
+        customer_style.clone(),
+        FontDefinitions::default(),
+        ThemeBase::Theme("brand.eguitheme".to_owned()),
+        &resolver,
+    )
+    .expect("the base should resolve");
+    assert_eq!(
+        theme.style.keys().collect::<Vec<_>>(),
+        vec!["spacing.slider_width"],
+        "only the overridden key should be stored"
+    );
+
+    let (_, _, issues) = EguiTheme::new_with_base(
+        customer_style.clone(),
+        FontDefinitions::default(),
+        ThemeBase::Theme("brand.eguitheme".to_owned()),
+        &resolver,
+    )
+    .expect("the base should resolve")
+    .extract_with_report();
+    assert_eq!(
+        issues[0].kind,
+        ThemeIssueKind::UnresolvedBase,
+        "the unresolved base should be reported"
+    );
+
+    let (style, _) = theme
+        .resolve_base(&resolver)
+        .expect("the base should resolve")
+        .extract_strict()
+        .expect("the resolved theme should be complete");
+    assert_eq!(style, customer_style, "style should match");
+}
+
+#[test]
+fn test_child_removes_base_font() {
+    let resolver = |_: &str| {
+        EguiTheme::new_with_base(
+            Style::default(),
+            nacelle_fonts(),
+            ThemeBase::Dark,
+            &no_themes,
+        )
+    };
+    let theme = EguiTheme::new_with_base(
+        Style::default(),
+        FontDefinitions::default(),
+        ThemeBase::Theme("brand.eguitheme".to_owned()),
+        &resolver,
+    )
+    .expect("the base should resolve");
+    let (_, fonts) = theme
+        .resolve_base(&resolver)
+        .expect("the base should resolve")
+        .extract_strict()
+        .expect("the resolved theme should be complete");
+    assert_eq!(
+        fonts,
+        FontDefinitions::default(),
+        "the font and family removed by the child should not be inherited"
+    );
+
+    let (_, fonts) = EguiTheme::new_with_base(
+        Style::default(),
+        nacelle_fonts(),
+        ThemeBase::Theme("brand.eguitheme".to_owned()),
+        &resolver,
+    )
+    .and_then(|theme| theme.resolve_base(&resolver))
+    .expect("the base should resolve")
+    .extract_strict()
+    .expect("the resolved theme should be complete");
+    assert_eq!(
+        fonts,
+        nacelle_fonts(),
+        "unchanged fonts should be inherited"
+    );
+}
+
+#[test]
+fn test_base_cycle() {
+    let resolver = |name: &str| {
+        let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+        theme.base = Some(ThemeBase::Theme(name.to_owned()));
+        Ok(theme)
+    };
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.base = Some(ThemeBase::Theme("loop".to_owned()));
+    assert_eq!(
+        theme.resolve_base(&resolver).err(),
+        Some(ThemeError::BaseCycle("loop".to_owned())),
+        "the cycle should be detected"
+    );
+}
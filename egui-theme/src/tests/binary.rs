@@ -0,0 +1,112 @@
+use egui::{Color32, FontDefinitions, Style};
+
+use super::nacelle_fonts;
+use crate::theme::binary::decompress;
+use crate::{EguiTheme, ThemeBase, ThemeCompression, ThemeError};
+
+fn theme() -> (Style, FontDefinitions) {
+    let mut style = Style::default();
+    style.visuals.hyperlink_color = Color32::GOLD;
+    (style, nacelle_fonts())
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let (style, fonts) = theme();
+    for compression in [ThemeCompression::None, ThemeCompression::Deflate] {
+        let bytes = EguiTheme::new(style.clone(), fonts.clone())
+            .to_bytes(compression)
+            .expect("encoding failed");
+        assert!(EguiTheme::is_binary(&bytes), "the header should be written");
+        let (de_style, de_fonts) = EguiTheme::from_bytes(&bytes)
+            .expect("decoding failed")
+            .extract_strict()
+            .expect("the theme should load in strict mode");
+        assert_eq!(de_style, style, "{compression:?} style should match");
+        assert_eq!(de_fonts, fonts, "{compression:?} fonts should match");
+    }
+}
+
+#[test]
+fn test_binary_is_compact() {
+    let (style, fonts) = theme();
+    let font_len = fonts.font_data["Nacelle"].font.len();
+    let theme = EguiTheme::new(style, fonts);
+    let raw = theme
+        .to_bytes(ThemeCompression::None)
+        .expect("encoding failed");
+    let compressed = theme
+        .to_bytes(ThemeCompression::Deflate)
+        .expect("encoding failed");
+    assert!(
+        raw.len() < font_len + 16 * 1024,
+        "font bytes should be stored raw, {} bytes for a {font_len} byte font",
+        raw.len()
+    );
+    assert!(
+        compressed.len() < raw.len(),
+        "compression should shrink the theme"
+    );
+}
+
+#[test]
+fn test_binary_keeps_base() {
+    let theme = EguiTheme::new_with_base(
+        Style::default(),
+        FontDefinitions::default(),
+        ThemeBase::Light,
+        &|_: &str| unreachable!(),
+    )
+    .expect("built-in bases need no resolver");
+    let bytes = theme
+        .to_bytes(ThemeCompression::default())
+        .expect("encoding failed");
+    let theme = EguiTheme::from_bytes(&bytes).expect("decoding failed");
+    assert_eq!(theme.base(), Some(&ThemeBase::Light));
+}
+
+#[test]
+fn test_binary_rejects_invalid_data() {
+    let bytes = EguiTheme::new(Style::default(), FontDefinitions::default())
+        .to_bytes(ThemeCompression::Deflate)
+        .expect("encoding failed");
+    let mut future = bytes.clone();
+    future[4] = u8::MAX;
+    let truncated = &bytes[..bytes.len() / 2];
+    for bytes in [b"(style: {})".as_slice(), &future, truncated] {
+        match EguiTheme::from_bytes(bytes) {
+            Err(ThemeError::InvalidBinary(_)) => {}
+            other => panic!("expected an invalid binary error, got {:?}", other.err()),
+        }
+    }
+}
+
+#[test]
+fn test_decompression_is_limited() {
+    let payload = miniz_oxide::deflate::compress_to_vec(&[0; 64 * 1024], 10);
+    assert_eq!(
+        decompress(&payload, 64 * 1024).map(|payload| payload.len()),
+        Ok(64 * 1024)
+    );
+    assert_eq!(
+        decompress(&payload, 1024),
+        Err(ThemeError::InvalidBinary(
+            "the payload is larger than 1024 bytes".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn test_load_detects_binary() {
+    let (style, fonts) = theme();
+    let path = std::env::temp_dir().join(format!("egui-theme-binary-{}.bin", std::process::id()));
+    let bytes = EguiTheme::new(style.clone(), fonts.clone())
+        .to_bytes(ThemeCompression::Deflate)
+        .expect("encoding failed");
+    std::fs::write(&path, bytes).expect("failed to write the theme");
+    let loaded = EguiTheme::load(&path);
+    std::fs::remove_file(&path).ok();
+    let (de_style, de_fonts) = loaded.expect("failed to load").extract();
+    assert_eq!(de_style, style, "style should match");
+    assert_eq!(de_fonts, fonts, "fonts should match");
+}
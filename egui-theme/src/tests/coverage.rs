@@ -0,0 +1,41 @@
+use egui::{FontDefinitions, FontFamily};
+
+use super::nacelle_fonts;
+use crate::{missing_glyphs, UNICODE_RANGES};
+
+fn fonts() -> FontDefinitions {
+    let mut fonts = nacelle_fonts();
+    fonts
+        .families
+        .insert(FontFamily::Name("Empty".into()), Vec::new());
+    fonts
+}
+
+#[test]
+fn test_missing_glyphs_in_text() {
+    let missing = missing_glyphs(&fonts(), "Hello\nПривет 世界".chars());
+    assert_eq!(missing.len(), 4, "every family should be reported");
+    assert_eq!(
+        missing[&FontFamily::Name("Nacelle".into())],
+        vec!['П', 'в', 'е', 'и', 'р', 'т', '世', '界'],
+        "only the Latin characters are covered"
+    );
+    assert_eq!(
+        missing[&FontFamily::Proportional],
+        vec!['世', '界'],
+        "the default fonts cover Cyrillic but not CJK"
+    );
+    assert_eq!(
+        missing[&FontFamily::Name("Empty".into())].len(),
+        "HeloПривет世界 ".chars().count(),
+        "a family without fonts cannot render anything"
+    );
+}
+
+#[test]
+fn test_missing_glyphs_in_range() {
+    let basic_latin = UNICODE_RANGES[0];
+    let missing = missing_glyphs(&fonts(), basic_latin.chars());
+    assert!(missing[&FontFamily::Name("Nacelle".into())].is_empty());
+    assert!(missing[&FontFamily::Monospace].is_empty());
+}
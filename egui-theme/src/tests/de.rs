@@ -1,6 +1,6 @@
-use egui::{FontDefinitions, FontFamily, Style};
+use egui::{FontDefinitions, FontFamily, Style, TextStyle};
 
-use crate::EguiTheme;
+use crate::{EguiTheme, ThemeError, ThemeIssue, ThemeIssueKind, ThemeSection};
 
 #[test]
 fn test_serialize_default() {
@@ -126,3 +126,147 @@ fn test_deserialize_incorrect_fields() {
     assert_eq!(style, default_style, "default should match");
     assert_eq!(fonts, default_fonts, "default should match");
 }
+
+#[test]
+fn test_report_default_theme() {
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    let (_, _, issues) = theme.extract_with_report();
+    assert!(issues.is_empty(), "unexpected issues: {issues:?}");
+}
+
+#[test]
+fn test_report_incorrect_parameters() {
+    let theme = include_str!("test-themes/incorrect_parameters.ron");
+    let deserialized_theme = ron::from_str::<EguiTheme>(theme).expect("failed to deserialize");
+    let (_, _, issues) = deserialized_theme.extract_with_report();
+    assert!(
+        issues.contains(&ThemeIssue {
+            section: ThemeSection::Style,
+            key: "thisisawrongparam".to_owned(),
+            kind: ThemeIssueKind::UnknownKey,
+        }),
+        "`thisisawrongparam` should be reported as unknown"
+    );
+    assert!(
+        !issues.iter().any(|issue| issue.key == "visuals.dark_mode"),
+        "`visuals.dark_mode` is valid and should not be reported"
+    );
+}
+
+#[test]
+fn test_report_incorrect_fields() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.style.insert(
+        "spacing.tooltip_width".to_owned(),
+        serde_json::json!("this is a wrong field"),
+    );
+    theme
+        .fonts
+        .insert("families".to_owned(), serde_json::json!(42));
+    let (style, fonts, issues) = theme.extract_with_report();
+    assert_eq!(style, Style::default(), "default should match");
+    assert_eq!(fonts, FontDefinitions::default(), "default should match");
+    assert_eq!(issues.len(), 2, "unexpected issues: {issues:?}");
+    assert!(
+        matches!(
+            &issues[0],
+            ThemeIssue { section: ThemeSection::Style, key, kind: ThemeIssueKind::InvalidValue(_) }
+                if key == "spacing.tooltip_width"
+        ),
+        "`spacing.tooltip_width` should be reported as invalid"
+    );
+    assert!(
+        matches!(
+            &issues[1],
+            ThemeIssue { section: ThemeSection::Fonts, key, kind: ThemeIssueKind::InvalidValue(_) }
+                if key == "families"
+        ),
+        "`families` should be reported as invalid"
+    );
+}
+
+#[test]
+fn test_report_missing_data() {
+    let theme = include_str!("test-themes/missing_data.ron");
+    let deserialized_theme = ron::from_str::<EguiTheme>(theme).expect("failed to deserialize");
+    let (_, _, issues) = deserialized_theme.extract_with_report();
+    assert!(
+        issues.contains(&ThemeIssue {
+            section: ThemeSection::Style,
+            key: "visuals.resize_corner_size".to_owned(),
+            kind: ThemeIssueKind::MissingKey,
+        }),
+        "`visuals.resize_corner_size` should be reported as missing"
+    );
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.section == ThemeSection::Fonts
+                && issue.key == "families"
+                && matches!(issue.kind, ThemeIssueKind::InvalidValue(_))),
+        "`families` is stored in an outdated format and should be reported as invalid"
+    );
+}
+
+#[test]
+fn test_strict_round_trip() {
+    let mut style = Style::default();
+    style.spacing.slider_width = 120.0;
+    let theme = EguiTheme::new(style.clone(), FontDefinitions::default());
+    let serialized = ron::to_string(&theme).expect("serialization failed");
+    let deserialized = ron::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+    let (de_style, de_fonts) = deserialized
+        .extract_strict()
+        .expect("a freshly saved theme should load in strict mode");
+    assert_eq!(de_style, style, "style should match");
+    assert_eq!(de_fonts, FontDefinitions::default(), "fonts should match");
+}
+
+#[test]
+fn test_strict_removed_text_styles() {
+    let mut style = Style::default();
+    style.text_styles.remove(&TextStyle::Heading);
+    style.text_styles.remove(&TextStyle::Monospace);
+    let theme = EguiTheme::new(style.clone(), FontDefinitions::default());
+    let serialized = ron::to_string(&theme).expect("serialization failed");
+    let deserialized = ron::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+    let (de_style, _) = deserialized
+        .extract_strict()
+        .expect("a freshly saved theme should load in strict mode");
+    assert_eq!(
+        de_style.text_styles, style.text_styles,
+        "removed text styles should not come back"
+    );
+}
+
+#[test]
+fn test_strict_missing_data() {
+    let theme = include_str!("test-themes/missing_data.ron");
+    let deserialized_theme = ron::from_str::<EguiTheme>(theme).expect("failed to deserialize");
+    match deserialized_theme.extract_strict() {
+        Err(ThemeError::Strict(issues)) => assert!(
+            issues
+                .iter()
+                .any(|issue| issue.kind == ThemeIssueKind::MissingKey),
+            "missing keys should be reported"
+        ),
+        other => panic!("strict mode should reject a theme with missing data, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_strict_incorrect_fields() {
+    let theme = include_str!("test-themes/incorrect_fields.ron");
+    let deserialized_theme = ron::from_str::<EguiTheme>(theme).expect("failed to deserialize");
+    match deserialized_theme.extract_strict() {
+        Err(ThemeError::Strict(issues)) => assert!(
+            issues.contains(&ThemeIssue {
+                section: ThemeSection::Style,
+                key: "style.spacing.tooltip_width".to_owned(),
+                kind: ThemeIssueKind::UnknownKey,
+            }),
+            "unrecognised keys should be reported"
+        ),
+        other => panic!("strict mode should reject a theme with incorrect fields, got {other:?}"),
+    }
+}
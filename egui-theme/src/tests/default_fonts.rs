@@ -0,0 +1,109 @@
+use egui::{FontData, FontDefinitions, FontFamily};
+
+use super::NACELLE;
+use crate::{builtin_font_names, is_builtin_font, remove_font, restore_default_fonts, EguiTheme};
+
+#[test]
+fn test_builtin_fonts_match_egui() {
+    let names = builtin_font_names();
+    assert_eq!(
+        names,
+        FontDefinitions::default().font_data.into_keys().collect(),
+        "the built-in fonts are derived from egui"
+    );
+    assert!(names.iter().all(|name| is_builtin_font(name)));
+    assert!(!is_builtin_font("Nacelle"));
+}
+
+#[test]
+fn test_replaced_builtin_font_is_serialized() {
+    let name = builtin_font_names().into_iter().next().unwrap();
+    let mut fonts = FontDefinitions::default();
+    fonts
+        .font_data
+        .insert(name.clone(), FontData::from_static(NACELLE));
+    let theme = EguiTheme::new(egui::Style::default(), fonts.clone());
+    let (_, extracted) = theme.extract();
+    assert_eq!(
+        extracted.font_data[&name], fonts.font_data[&name],
+        "a font replacing a built-in one must not be dropped"
+    );
+
+    let theme = EguiTheme::new(egui::Style::default(), FontDefinitions::default());
+    let font_data = theme.fonts["font_data"].as_object().unwrap();
+    assert!(
+        font_data.is_empty(),
+        "unchanged built-in fonts are not saved"
+    );
+}
+
+#[test]
+fn test_restore_default_fonts() {
+    let defaults = FontDefinitions::default();
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.clear();
+    fonts
+        .font_data
+        .insert("Nacelle".to_owned(), FontData::from_static(NACELLE));
+    let proportional = fonts.families.get_mut(&FontFamily::Proportional).unwrap();
+    proportional.clear();
+    proportional.push("Nacelle".to_owned());
+    fonts.families.remove(&FontFamily::Monospace);
+
+    restore_default_fonts(&mut fonts);
+    assert!(
+        fonts.font_data.contains_key("Nacelle"),
+        "custom fonts are kept"
+    );
+    for (name, data) in defaults.font_data.iter() {
+        assert_eq!(&fonts.font_data[name], data);
+    }
+    let mut expected = vec!["Nacelle".to_owned()];
+    expected.extend(defaults.families[&FontFamily::Proportional].iter().cloned());
+    assert_eq!(fonts.families[&FontFamily::Proportional], expected);
+    assert_eq!(
+        fonts.families[&FontFamily::Monospace],
+        defaults.families[&FontFamily::Monospace]
+    );
+}
+
+#[test]
+fn test_removed_builtin_font_stays_removed() {
+    let name = builtin_font_names().into_iter().next().unwrap();
+    let mut fonts = FontDefinitions::default();
+    remove_font(&mut fonts, &name);
+    let theme = EguiTheme::new(egui::Style::default(), fonts.clone());
+    assert!(theme.fonts.contains_key("removed_fonts"));
+    let (_, extracted) = theme
+        .extract_strict()
+        .expect("a theme without a built-in font should load in strict mode");
+    assert_eq!(extracted, fonts, "the removed font should not come back");
+
+    let theme = EguiTheme::new(egui::Style::default(), FontDefinitions::default());
+    assert!(
+        !theme.fonts.contains_key("removed_fonts"),
+        "nothing is listed while every built-in font is there"
+    );
+}
+
+#[test]
+fn test_restore_default_fonts_keeps_tweaks() {
+    let mut names = builtin_font_names().into_iter();
+    let (tweaked, replaced) = (names.next().unwrap(), names.next().unwrap());
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.get_mut(&tweaked).unwrap().tweak.scale = 2.0;
+    fonts
+        .font_data
+        .insert(replaced.clone(), FontData::from_static(NACELLE));
+
+    restore_default_fonts(&mut fonts);
+    assert_eq!(
+        fonts.font_data[&tweaked].tweak.scale, 2.0,
+        "the tweak of an unchanged built-in font is kept"
+    );
+    assert_eq!(
+        fonts.font_data[&replaced],
+        FontDefinitions::default().font_data[&replaced],
+        "a replaced built-in font is restored"
+    );
+}
@@ -0,0 +1,93 @@
+use egui::{Color32, FontData, FontDefinitions, FontFamily, Style};
+
+use super::NACELLE;
+use crate::{diff, diff_themes, EguiTheme, ThemeChange};
+
+#[test]
+fn test_diff_identical() {
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    let result = diff_themes(&theme, &theme);
+    assert!(result.is_empty(), "unexpected changes: {result}");
+}
+
+#[test]
+fn test_diff_style() {
+    let old = Style::default();
+    let mut new = Style::default();
+    new.visuals.widgets.hovered.bg_fill = Color32::RED;
+    let fonts = FontDefinitions::default();
+    let result = diff((&old, &fonts), (&new, &fonts));
+    assert_eq!(
+        result.changes,
+        vec![ThemeChange::Style {
+            key: "visuals.widgets.hovered.bg_fill".to_owned(),
+            old: Some(
+                serde_json::to_value(old.visuals.widgets.hovered.bg_fill)
+                    .expect("serialization failed")
+            ),
+            new: Some(serde_json::json!([255, 0, 0, 255])),
+        }],
+        "only the leaf value should be reported"
+    );
+    assert!(
+        result
+            .to_string()
+            .starts_with("~ visuals.widgets.hovered.bg_fill: "),
+        "unexpected report: {result}"
+    );
+}
+
+#[test]
+fn test_diff_fonts() {
+    let style = Style::default();
+    let old = FontDefinitions::default();
+    let mut new = FontDefinitions::default();
+    new.font_data.remove("Hack");
+    new.font_data
+        .insert("Nacelle".to_owned(), FontData::from_static(NACELLE));
+    new.families
+        .get_mut(&FontFamily::Proportional)
+        .expect("Proportional should exist")
+        .reverse();
+    new.families
+        .get_mut(&FontFamily::Monospace)
+        .expect("Monospace should exist")
+        .push("Nacelle".to_owned());
+    new.families
+        .insert(FontFamily::Name("Icons".into()), vec!["Nacelle".to_owned()]);
+
+    let result = diff((&style, &old), (&style, &new));
+    assert!(
+        result
+            .changes
+            .contains(&ThemeChange::FontRemoved("Hack".to_owned())),
+        "Hack should be removed"
+    );
+    assert!(
+        result
+            .changes
+            .contains(&ThemeChange::FontAdded("Nacelle".to_owned())),
+        "Nacelle should be added"
+    );
+    assert!(
+        result.changes.iter().any(|change| matches!(
+            change,
+            ThemeChange::FamilyReordered { family, .. } if family == "Proportional"
+        )),
+        "Proportional should be reordered"
+    );
+    assert!(
+        result.changes.iter().any(|change| matches!(
+            change,
+            ThemeChange::FamilyChanged { family, .. } if family == "Monospace"
+        )),
+        "Monospace should be changed"
+    );
+    assert!(
+        result.changes.contains(&ThemeChange::FamilyAdded {
+            family: "Icons".to_owned(),
+            fonts: vec!["Nacelle".to_owned()],
+        }),
+        "Icons should be added"
+    );
+}
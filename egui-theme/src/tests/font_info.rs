@@ -0,0 +1,87 @@
+use egui::{FontData, FontDefinitions, FontFamily, Style};
+
+use super::NACELLE;
+use crate::{validate_font, EguiTheme, FontError, FontInfo, ThemeIssueKind};
+
+#[test]
+fn test_validate_font() {
+    assert_eq!(
+        validate_font(NACELLE, 0),
+        Ok(()),
+        "the test font should be valid"
+    );
+    assert!(
+        matches!(
+            validate_font(b"definitely not a font", 0),
+            Err(FontError::Parse(_))
+        ),
+        "arbitrary bytes should not parse"
+    );
+    assert!(
+        matches!(validate_font(NACELLE, 3), Err(FontError::Parse(_))),
+        "the font only has a single face"
+    );
+    assert!(
+        matches!(validate_font(&NACELLE[..64], 0), Err(FontError::Parse(_))),
+        "a truncated font should not parse"
+    );
+}
+
+#[test]
+fn test_invalid_font_is_reported() {
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.insert(
+        "Broken".to_owned(),
+        FontData::from_static(b"definitely not a font"),
+    );
+    fonts
+        .families
+        .get_mut(&FontFamily::Proportional)
+        .expect("the proportional family should exist")
+        .insert(0, "Broken".to_owned());
+    let theme = EguiTheme::new(Style::default(), fonts);
+
+    let (_, fonts, issues) = theme.extract_with_report();
+    assert_eq!(issues.len(), 1, "only the broken font should be reported");
+    assert_eq!(issues[0].key, "Broken");
+    assert!(
+        matches!(issues[0].kind, ThemeIssueKind::InvalidFont(_)),
+        "expected an invalid font, got {:?}",
+        issues[0].kind
+    );
+    assert_eq!(
+        fonts,
+        FontDefinitions::default(),
+        "the broken font should be left out of the definitions and families"
+    );
+}
+
+#[test]
+fn test_font_info() {
+    let info = FontInfo::parse(NACELLE, 0).expect("the test font should parse");
+    assert_eq!(info.family_name.as_deref(), Some("Nacelle"));
+    assert_eq!(info.style_name.as_deref(), Some("Regular"));
+    assert_eq!(info.weight, 400);
+    assert!(!info.italic && !info.monospaced);
+    assert_eq!(info.glyph_count, 365);
+    assert_eq!(info.file_size, NACELLE.len());
+    assert_eq!(info.face_count, 1);
+
+    let covered = |name: &str| {
+        info.coverage
+            .iter()
+            .find(|coverage| coverage.range.name == name)
+            .map(|coverage| coverage.covered)
+    };
+    assert_eq!(
+        covered("Basic Latin"),
+        Some(95),
+        "ASCII should be fully covered"
+    );
+    assert_eq!(covered("Cyrillic"), None, "the font has no Cyrillic glyphs");
+    assert_eq!(
+        covered("CJK Unified Ideographs"),
+        None,
+        "the font has no CJK glyphs"
+    );
+}
@@ -0,0 +1,98 @@
+use egui::{FontData, FontDefinitions, FontFamily, Style};
+
+use super::{nacelle_fonts, NACELLE};
+use crate::{EguiTheme, FontSource, ThemeError, ThemeIssue, ThemeIssueKind, ThemeSection};
+
+/// The same font registered under two names with different tweaks.
+fn fonts() -> FontDefinitions {
+    let mut fonts = nacelle_fonts();
+    let mut large = FontData::from_static(NACELLE);
+    large.tweak.scale = 2.0;
+    fonts.font_data.insert("Nacelle Large".to_owned(), large);
+    fonts.families.insert(
+        FontFamily::Name("Nacelle".into()),
+        vec!["Nacelle Large".to_owned(), "Nacelle".to_owned()],
+    );
+    fonts
+}
+
+#[test]
+fn test_identical_fonts_are_stored_once() {
+    let theme = EguiTheme::new(Style::default(), fonts());
+    let font_data = theme.fonts["font_data"]
+        .as_object()
+        .expect("font_data should be an object");
+    assert_eq!(
+        font_data.len(),
+        1,
+        "the font data should only be stored once"
+    );
+    assert!(
+        theme.fonts["font_aliases"].get("Nacelle Large").is_some(),
+        "the second name should be an alias"
+    );
+    let serialized = serde_json::to_string(&theme).expect("serialization failed");
+    let (_, de_fonts) = serde_json::from_str::<EguiTheme>(&serialized)
+        .expect("failed to deserialize")
+        .extract_strict()
+        .expect("the theme should load in strict mode");
+    assert_eq!(de_fonts, fonts(), "both names should be restored");
+}
+
+#[test]
+fn test_corrupt_font_is_reported() {
+    let mut theme = EguiTheme::new(Style::default(), fonts());
+    let font = theme
+        .fonts
+        .get_mut("font_data")
+        .and_then(|font_data| font_data.get_mut("Nacelle"))
+        .and_then(|data| data.get_mut("font"))
+        .and_then(|font| font.as_array_mut())
+        .expect("the font should be embedded");
+    font.truncate(font.len() / 2);
+
+    let (_, fonts, issues) = theme.extract_with_report();
+    assert_eq!(
+        issues,
+        vec![ThemeIssue::new(
+            ThemeSection::Fonts,
+            "Nacelle",
+            ThemeIssueKind::CorruptFont
+        )],
+        "only the corrupt font should be reported"
+    );
+    assert!(!fonts.font_data.contains_key("Nacelle"));
+    assert!(
+        !fonts.font_data.contains_key("Nacelle Large"),
+        "aliases of a corrupt font should be dropped"
+    );
+    assert_eq!(
+        fonts.families[&FontFamily::Name("Nacelle".into())],
+        Vec::<String>::new(),
+        "corrupt fonts should be removed from their families"
+    );
+}
+
+#[test]
+fn test_reference_shared_font() {
+    let mut theme = EguiTheme::new(Style::default(), fonts());
+    theme
+        .reference_font("Nacelle Large", FontSource::hash_of(NACELLE))
+        .expect("the alias should be referenced");
+    let references = theme.font_references();
+    assert_eq!(
+        references.keys().collect::<Vec<_>>(),
+        vec!["Nacelle", "Nacelle Large"],
+        "every name sharing the data should be referenced"
+    );
+    assert!(!theme.fonts.contains_key("font_aliases"));
+    assert!(!theme.fonts.contains_key("font_hashes"));
+
+    let resolver = |_: &FontSource| -> Result<Vec<u8>, ThemeError> { Ok(NACELLE.to_vec()) };
+    let (_, de_fonts) = theme
+        .resolve_fonts(&resolver)
+        .expect("the fonts should resolve")
+        .extract_strict()
+        .expect("the theme should load in strict mode");
+    assert_eq!(de_fonts, fonts());
+}
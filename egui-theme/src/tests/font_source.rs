@@ -0,0 +1,110 @@
+use egui::{FontData, FontDefinitions, FontFamily, Style};
+
+use super::NACELLE;
+use crate::{EguiTheme, FontSource, ThemeError, ThemeIssue, ThemeIssueKind, ThemeSection};
+
+fn fonts() -> FontDefinitions {
+    let mut fonts = FontDefinitions::default();
+    let mut data = FontData::from_static(NACELLE);
+    data.tweak.scale = 1.5;
+    fonts.font_data.insert("Nacelle".to_owned(), data);
+    fonts
+        .families
+        .get_mut(&FontFamily::Proportional)
+        .expect("the proportional family should exist")
+        .insert(0, "Nacelle".to_owned());
+    fonts
+}
+
+fn resolver(source: &FontSource) -> Result<Vec<u8>, ThemeError> {
+    match source {
+        FontSource::Path(path) if path == "fonts/Nacelle-Regular.otf" => Ok(NACELLE.to_vec()),
+        FontSource::Asset(id) if id == "nacelle" => Ok(NACELLE.to_vec()),
+        FontSource::Hash(_) => Ok(NACELLE.to_vec()),
+        other => Err(ThemeError::MissingKey(other.to_string())),
+    }
+}
+
+#[test]
+fn test_reference_sources() {
+    let sources = [
+        FontSource::Path("fonts/Nacelle-Regular.otf".to_owned()),
+        FontSource::Asset("nacelle".to_owned()),
+        FontSource::hash_of(NACELLE),
+    ];
+    for source in sources {
+        let mut theme = EguiTheme::new(Style::default(), fonts());
+        theme
+            .reference_font("Nacelle", source.clone())
+            .expect("the font should be referenced");
+        assert_eq!(
+            theme.font_references()["Nacelle"].source,
+            source,
+            "the reference should be stored"
+        );
+        let serialized = serde_json::to_string(&theme).expect("serialization failed");
+        assert!(
+            serialized.len() < NACELLE.len(),
+            "the font bytes should not be embedded"
+        );
+
+        let theme = serde_json::from_str::<EguiTheme>(&serialized)
+            .expect("failed to deserialize")
+            .resolve_fonts(&resolver)
+            .expect("the font should resolve");
+        assert!(theme.font_references().is_empty());
+        let (_, de_fonts) = theme
+            .extract_strict()
+            .expect("the theme should load in strict mode");
+        assert_eq!(
+            de_fonts,
+            fonts(),
+            "{source} should resolve to the same fonts"
+        );
+    }
+}
+
+#[test]
+fn test_hash_mismatch() {
+    let mut theme = EguiTheme::new(Style::default(), fonts());
+    assert_eq!(
+        theme.reference_font("Nacelle", FontSource::hash_of(b"not a font")),
+        Err(ThemeError::FontHashMismatch("Nacelle".to_owned())),
+        "the hash should be checked against the embedded font"
+    );
+    theme
+        .reference_font("Nacelle", FontSource::hash_of(NACELLE))
+        .expect("the font should be referenced");
+    let truncated = |_: &FontSource| Ok(NACELLE[..NACELLE.len() / 2].to_vec());
+    assert_eq!(
+        theme.resolve_fonts(&truncated).err(),
+        Some(ThemeError::FontHashMismatch("Nacelle".to_owned())),
+        "the resolved bytes should be checked against the hash"
+    );
+}
+
+#[test]
+fn test_unresolved_font() {
+    let mut theme = EguiTheme::new(Style::default(), fonts());
+    theme
+        .reference_font("Nacelle", FontSource::Asset("nacelle".to_owned()))
+        .expect("the font should be referenced");
+    assert_eq!(
+        theme.reference_font("Missing", FontSource::Asset("missing".to_owned())),
+        Err(ThemeError::MissingKey("Missing".to_owned()))
+    );
+    let (_, fonts, issues) = theme.extract_with_report();
+    assert_eq!(
+        issues,
+        vec![ThemeIssue::new(
+            ThemeSection::Fonts,
+            "Nacelle",
+            ThemeIssueKind::UnresolvedFont
+        )]
+    );
+    assert_eq!(
+        fonts,
+        FontDefinitions::default(),
+        "the unresolved font should be left out of the families"
+    );
+}
@@ -0,0 +1,102 @@
+use egui::{FontData, FontDefinitions, FontTweak, Style};
+
+use super::{enabled_formats, NACELLE};
+use crate::{builtin_font_names, remove_font, EguiTheme, ThemeFormat, ThemeIssueKind};
+
+const TWEAK: FontTweak = FontTweak {
+    scale: 0.85,
+    y_offset_factor: 0.1,
+    y_offset: -1.5,
+};
+
+fn round_trip(fonts: FontDefinitions, format: ThemeFormat) -> FontDefinitions {
+    let theme = EguiTheme::new(Style::default(), fonts);
+    let serialized = theme
+        .to_string_as(format)
+        .unwrap_or_else(|error| panic!("{format} serialization failed: {error}"));
+    let (_, fonts, issues) = EguiTheme::from_str_as(&serialized, format)
+        .unwrap_or_else(|error| panic!("{format} deserialization failed: {error}"))
+        .extract_with_report();
+    assert!(issues.is_empty(), "{format}: {issues:?}");
+    fonts
+}
+
+#[test]
+fn test_custom_font_tweaks_round_trip() {
+    let mut fonts = FontDefinitions::default();
+    let nacelle = FontData::from_static(NACELLE);
+    fonts
+        .font_data
+        .insert("Nacelle".to_owned(), nacelle.clone().tweak(TWEAK));
+    // An alias shares the data but has its own tweak.
+    fonts.font_data.insert(
+        "Nacelle Small".to_owned(),
+        nacelle.tweak(FontTweak {
+            scale: 0.5,
+            ..TWEAK
+        }),
+    );
+    for format in enabled_formats() {
+        let extracted = round_trip(fonts.clone(), format);
+        assert_eq!(extracted.font_data["Nacelle"].tweak, TWEAK, "{format}");
+        assert_eq!(
+            extracted.font_data["Nacelle Small"].tweak.scale, 0.5,
+            "{format}"
+        );
+    }
+}
+
+#[test]
+fn test_builtin_font_tweaks_round_trip() {
+    let name = builtin_font_names().into_iter().next().unwrap();
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.get_mut(&name).unwrap().tweak = TWEAK;
+
+    let theme = EguiTheme::new(Style::default(), fonts.clone());
+    assert!(
+        theme.fonts["font_data"].as_object().unwrap().is_empty(),
+        "only the tweak of a built-in font is saved"
+    );
+    assert!(theme.fonts.contains_key("font_tweaks"));
+
+    for format in enabled_formats() {
+        let extracted = round_trip(fonts.clone(), format);
+        assert_eq!(extracted.font_data[&name].tweak, TWEAK, "{format}");
+        assert_eq!(extracted.font_data, fonts.font_data, "{format}");
+    }
+}
+
+#[test]
+fn test_tweak_of_unknown_font() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.fonts.insert(
+        "font_tweaks".to_owned(),
+        serde_json::json!({ "Missing": TWEAK }),
+    );
+    let (_, _, issues) = theme.extract_with_report();
+    assert!(
+        issues.iter().any(|issue| issue.kind
+            == ThemeIssueKind::InvalidValue("`Missing` is an unknown font".to_owned())),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn test_tweak_of_removed_builtin_font() {
+    let name = builtin_font_names().into_iter().next().unwrap();
+    let mut fonts = FontDefinitions::default();
+    remove_font(&mut fonts, &name);
+    let mut theme = EguiTheme::new(Style::default(), fonts.clone());
+    theme.fonts.insert(
+        "font_tweaks".to_owned(),
+        serde_json::json!({ name.clone(): TWEAK }),
+    );
+    let (_, extracted, issues) = theme.extract_with_report();
+    assert_eq!(extracted, fonts, "the removed font should not come back");
+    assert_eq!(
+        issues.iter().map(|issue| &issue.kind).collect::<Vec<_>>(),
+        [&ThemeIssueKind::InvalidValue(format!(
+            "the built-in font `{name}` was removed from the theme"
+        ))]
+    );
+}
@@ -0,0 +1,131 @@
+use egui::{Color32, FontDefinitions, Style, TextStyle};
+
+use super::{enabled_formats, nacelle_fonts};
+use crate::{EguiTheme, ThemeError, ThemeFormat};
+
+fn theme() -> (Style, FontDefinitions) {
+    let mut style = Style::default();
+    style.visuals.override_text_color = Some(Color32::LIGHT_BLUE);
+    style.override_text_style = Some(TextStyle::Monospace);
+    style.wrap = None;
+    (style, nacelle_fonts())
+}
+
+#[test]
+fn test_format_round_trips() {
+    let (style, fonts) = theme();
+    for format in enabled_formats() {
+        let theme = EguiTheme::new(style.clone(), fonts.clone());
+        let serialized = theme
+            .to_string_as(format)
+            .unwrap_or_else(|error| panic!("{format} serialization failed: {error}"));
+        assert_eq!(
+            ThemeFormat::detect(&serialized),
+            Some(format),
+            "{format} should be detected from its contents"
+        );
+        let (de_style, de_fonts) = EguiTheme::from_str_detect(&serialized)
+            .unwrap_or_else(|error| panic!("{format} deserialization failed: {error}"))
+            .extract_strict()
+            .expect("the theme should load in strict mode");
+        assert_eq!(de_style, style, "{format} style should match");
+        assert_eq!(de_fonts, fonts, "{format} fonts should match");
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_unset_options() {
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    let serialized = theme
+        .to_string_as(ThemeFormat::Toml)
+        .expect("serialization failed");
+    let theme = EguiTheme::from_str_as(&serialized, ThemeFormat::Toml).expect("failed to load");
+    assert_eq!(
+        theme.get::<Option<TextStyle>>("override_text_style"),
+        Ok(None),
+        "unset options should be restored"
+    );
+}
+
+#[test]
+fn test_format_from_extension() {
+    let cases = [
+        ("theme.eguitheme", Some(ThemeFormat::Ron)),
+        ("theme.ron", Some(ThemeFormat::Ron)),
+        ("theme.JSON", Some(ThemeFormat::Json)),
+        ("theme.toml", Some(ThemeFormat::Toml)),
+        ("theme.yml", Some(ThemeFormat::Yaml)),
+        ("theme.yaml", Some(ThemeFormat::Yaml)),
+        ("theme.txt", None),
+        ("theme", None),
+    ];
+    for (path, format) in cases {
+        assert_eq!(
+            ThemeFormat::from_path(std::path::Path::new(path)),
+            format,
+            "wrong format for `{path}`"
+        );
+    }
+}
+
+#[test]
+fn test_detect_format() {
+    let cases = [
+        ("{\"style\": {}}", Some(ThemeFormat::Json)),
+        ("(\n  style: {},\n)", Some(ThemeFormat::Ron)),
+        ("---\nstyle: {}", Some(ThemeFormat::Yaml)),
+        ("egui_version: \"a = b\"", Some(ThemeFormat::Yaml)),
+        ("'quoted key': 1", Some(ThemeFormat::Yaml)),
+        ("egui_version = \"0.18: latest\"", Some(ThemeFormat::Toml)),
+        ("[style]\nwrap = true", Some(ThemeFormat::Toml)),
+        ("# comment\n\"quoted.key\" = 1", Some(ThemeFormat::Toml)),
+        ("not a theme = ", None),
+        ("http://example.com", None),
+    ];
+    for (contents, format) in cases {
+        assert_eq!(
+            ThemeFormat::detect(contents),
+            format,
+            "wrong format for `{contents}`"
+        );
+    }
+}
+
+#[test]
+fn test_unknown_format() {
+    assert_eq!(
+        EguiTheme::from_str_detect("").err(),
+        Some(ThemeError::UnknownFormat)
+    );
+    match EguiTheme::from_str_as("{", ThemeFormat::Json) {
+        Err(ThemeError::Format { format, .. }) => assert_eq!(format, ThemeFormat::Json),
+        other => panic!("expected a format error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_save_and_load() {
+    let (style, fonts) = theme();
+    let dir = std::env::temp_dir().join(format!("egui-theme-format-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("failed to create the temp dir");
+    let formats = enabled_formats();
+    let extensions = ThemeFormat::extensions().iter().filter(|extension| {
+        ThemeFormat::from_extension(extension).is_some_and(|format| formats.contains(&format))
+    });
+    for extension in extensions {
+        let path = dir.join(format!("theme.{extension}"));
+        EguiTheme::new(style.clone(), fonts.clone())
+            .save(&path)
+            .expect("failed to save");
+        let (de_style, de_fonts) = EguiTheme::load(&path).expect("failed to load").extract();
+        assert_eq!(de_style, style, "`{extension}` style should match");
+        assert_eq!(de_fonts, fonts, "`{extension}` fonts should match");
+    }
+    assert_eq!(
+        EguiTheme::new(style, fonts).save(dir.join("theme.bin")),
+        Err(ThemeError::UnknownFormat),
+        "unknown extensions should not be saved as RON"
+    );
+    std::fs::remove_dir_all(&dir).ok();
+}
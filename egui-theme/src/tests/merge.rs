@@ -0,0 +1,126 @@
+use egui::{Color32, FontData, FontDefinitions, Style};
+
+use super::{nacelle_fonts, NACELLE};
+use crate::{merge_themes, remove_font, EguiTheme, ThemeSection};
+
+#[test]
+fn test_merge_without_conflicts() {
+    let base = Style::default();
+    let mut ours = base.clone();
+    ours.visuals.hyperlink_color = Color32::RED;
+    ours.visuals.widgets.hovered.bg_fill = Color32::GREEN;
+    let mut theirs = base.clone();
+    theirs.visuals.widgets.active.bg_fill = Color32::BLUE;
+    theirs.spacing.slider_width = 42.0;
+
+    let result = merge_themes(
+        &EguiTheme::new(base, FontDefinitions::default()),
+        &EguiTheme::new(ours.clone(), FontDefinitions::default()),
+        &EguiTheme::new(theirs, FontDefinitions::default()),
+    );
+    assert!(
+        result.conflicts.is_empty(),
+        "unexpected conflicts: {:?}",
+        result.conflicts
+    );
+    let (style, _) = result
+        .theme
+        .extract_strict()
+        .expect("the merged theme should be complete");
+    let mut expected = ours;
+    expected.visuals.widgets.active.bg_fill = Color32::BLUE;
+    expected.spacing.slider_width = 42.0;
+    assert_eq!(style, expected, "both sides should be merged");
+}
+
+#[test]
+fn test_merge_conflicts() {
+    let base = Style::default();
+    let mut ours = base.clone();
+    ours.visuals.widgets.hovered.bg_fill = Color32::RED;
+    ours.animation_time = 0.5;
+    let mut theirs = base.clone();
+    theirs.visuals.widgets.hovered.bg_fill = Color32::BLUE;
+    theirs.animation_time = 0.5;
+
+    let result = merge_themes(
+        &EguiTheme::new(base.clone(), FontDefinitions::default()),
+        &EguiTheme::new(ours, FontDefinitions::default()),
+        &EguiTheme::new(theirs, FontDefinitions::default()),
+    );
+    assert_eq!(result.conflicts.len(), 1, "only one value should conflict");
+    let conflict = &result.conflicts[0];
+    assert_eq!(
+        conflict.section,
+        ThemeSection::Style,
+        "section should match"
+    );
+    assert_eq!(
+        conflict.key, "visuals.widgets.hovered.bg_fill",
+        "the leaf value should conflict"
+    );
+    assert_eq!(
+        conflict.base,
+        serde_json::to_value(base.visuals.widgets.hovered.bg_fill).ok(),
+        "base value should match"
+    );
+    assert_eq!(
+        conflict.ours,
+        Some(serde_json::json!([255, 0, 0, 255])),
+        "our value should match"
+    );
+    assert_eq!(
+        conflict.theirs,
+        Some(serde_json::json!([0, 0, 255, 255])),
+        "their value should match"
+    );
+
+    let (style, _) = result.theme.extract();
+    assert_eq!(
+        style.visuals.widgets.hovered.bg_fill,
+        Color32::RED,
+        "conflicts should keep our value"
+    );
+    assert_eq!(style.animation_time, 0.5, "identical changes should merge");
+}
+
+#[test]
+fn test_merge_fonts_as_a_whole() {
+    let base = nacelle_fonts();
+    // The copy has the same bytes, so it is stored as an alias of `Nacelle`.
+    let mut ours = base.clone();
+    ours.font_data
+        .insert("Nacelle Copy".to_owned(), FontData::from_static(NACELLE));
+    let mut theirs = base.clone();
+    remove_font(&mut theirs, "Nacelle");
+
+    let result = merge_themes(
+        &EguiTheme::new(Style::default(), base.clone()),
+        &EguiTheme::new(Style::default(), ours.clone()),
+        &EguiTheme::new(Style::default(), theirs.clone()),
+    );
+    assert_eq!(result.conflicts.len(), 1, "{:?}", result.conflicts);
+    assert_eq!(result.conflicts[0].section, ThemeSection::Fonts);
+    assert_eq!(result.conflicts[0].key, "font_data");
+    let (_, fonts, issues) = result.theme.extract_with_report();
+    assert!(issues.is_empty(), "{issues:?}");
+    assert_eq!(fonts, ours, "conflicting fonts should keep ours as a whole");
+
+    // Fonts changed on one side only merge with style changes from the other.
+    let style = Style {
+        animation_time: 0.5,
+        ..Style::default()
+    };
+    let result = merge_themes(
+        &EguiTheme::new(Style::default(), base.clone()),
+        &EguiTheme::new(Style::default(), theirs.clone()),
+        &EguiTheme::new(style.clone(), base),
+    );
+    assert!(result.conflicts.is_empty(), "{:?}", result.conflicts);
+    let (merged_style, fonts) = result
+        .theme
+        .extract_strict()
+        .expect("the merged theme should be complete");
+    assert_eq!(merged_style, style);
+    assert_eq!(fonts, theirs);
+}
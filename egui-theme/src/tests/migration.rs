@@ -0,0 +1,129 @@
+use egui::{FontDefinitions, Style};
+
+use crate::{Compatibility, EguiTheme, Migration, MigrationRegistry, ThemeError};
+
+#[test]
+fn test_compatibility_exact() {
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    assert_eq!(
+        theme.compatibility(&MigrationRegistry::default()),
+        Compatibility::Exact,
+        "a new theme should be an exact match"
+    );
+}
+
+#[test]
+fn test_compatibility_compatible() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.egui_theme_version = "0.1.0".to_owned();
+    assert_eq!(
+        theme.compatibility(&MigrationRegistry::default()),
+        Compatibility::Compatible,
+        "only the egui version should matter for compatibility"
+    );
+}
+
+#[test]
+fn test_compatibility_unsupported() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.egui_version = "99.0".to_owned();
+    assert_eq!(
+        theme.compatibility(&MigrationRegistry::default()),
+        Compatibility::Unsupported,
+        "newer egui versions should be unsupported"
+    );
+    theme.egui_version = "0.17".to_owned();
+    assert_eq!(
+        theme.compatibility(&MigrationRegistry::default()),
+        Compatibility::Unsupported,
+        "older egui versions without a migration should be unsupported"
+    );
+    theme.egui_version = "not a version".to_owned();
+    assert_eq!(
+        theme.compatibility(&MigrationRegistry::default()),
+        Compatibility::Unsupported,
+        "unparsable versions should be unsupported"
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.egui_version = "0.16.1".to_owned();
+    let value = theme
+        .style
+        .remove("visuals.hyperlink_color")
+        .expect("hyperlink_color should exist");
+    theme.style.insert("visuals.link_color".to_owned(), value);
+
+    let mut registry = MigrationRegistry::default();
+    registry.register(Migration::new("0.16", "0.17", |_, _| {}).unwrap());
+    registry.register(
+        Migration::rename_style_keys(
+            "0.17",
+            crate::EGUI_VERSION,
+            &[("visuals.link_color", "visuals.hyperlink_color")],
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        theme.compatibility(&registry),
+        Compatibility::NeedsMigration,
+        "the theme should need a migration"
+    );
+
+    theme.migrate(&registry).expect("migration should succeed");
+    assert_eq!(
+        theme.egui_version(),
+        crate::EGUI_VERSION,
+        "version should be updated"
+    );
+    assert_eq!(
+        theme.compatibility(&registry),
+        Compatibility::Exact,
+        "the theme should be up to date"
+    );
+    let (_, _, issues) = theme.extract_with_report();
+    assert!(issues.is_empty(), "unexpected issues: {issues:?}");
+}
+
+#[test]
+fn test_migrate_unsupported() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.egui_version = "0.15".to_owned();
+    assert_eq!(
+        theme.migrate(&MigrationRegistry::default()),
+        Err(ThemeError::UnsupportedVersion("0.15".to_owned())),
+        "no migration path should exist"
+    );
+    assert_eq!(theme.egui_version(), "0.15", "version should be untouched");
+}
+
+#[test]
+fn test_migration_path() {
+    let mut theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    theme.egui_version = "0.16".to_owned();
+    let mut registry = MigrationRegistry::default();
+    // Overshoots the running version, so it must not be picked.
+    registry.register(Migration::new("0.16", "99.0", |_, _| {}).unwrap());
+    registry.register(Migration::new("0.16", "0.17", |_, _| {}).unwrap());
+    registry.register(Migration::new("0.17", crate::EGUI_VERSION, |_, _| {}).unwrap());
+    assert_eq!(
+        theme.compatibility(&registry),
+        Compatibility::NeedsMigration,
+        "the path through 0.17 should be found"
+    );
+    theme.migrate(&registry).expect("migration should succeed");
+    assert_eq!(theme.egui_version(), crate::EGUI_VERSION);
+}
+
+#[test]
+fn test_invalid_migration_version() {
+    assert!(
+        matches!(
+            Migration::new("zero", "0.18", |_, _| {}),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "zero"
+        ),
+        "the version cannot be parsed"
+    );
+}
@@ -1,6 +1,58 @@
-use crate::EguiTheme;
+use egui::{FontData, FontDefinitions, FontFamily};
+
+use crate::{EguiTheme, ThemeFormat};
+mod access;
+mod base;
+#[cfg(feature = "binary")]
+mod binary;
+mod coverage;
 mod de;
+mod default_fonts;
+mod diff;
+mod font_info;
+mod font_integrity;
+mod font_source;
+mod font_tweak;
+mod format;
+mod merge;
+mod migration;
+mod references;
+mod rename;
+mod roundtrip;
 mod ser;
+mod sparse;
+#[cfg(feature = "subset")]
+mod subset;
+mod system_fonts;
+
+/// Every format enabled by the features of this build.
+pub(crate) fn enabled_formats() -> Vec<ThemeFormat> {
+    vec![
+        ThemeFormat::Json,
+        #[cfg(feature = "ron")]
+        ThemeFormat::Ron,
+        #[cfg(feature = "toml")]
+        ThemeFormat::Toml,
+        #[cfg(feature = "yaml")]
+        ThemeFormat::Yaml,
+    ]
+}
+
+pub(crate) const NACELLE: &[u8] = include_bytes!("test-fonts/Nacelle-Regular.otf");
+
+/// The default fonts plus the Nacelle test font, registered as `Nacelle` and in a family of the same name.
+pub(crate) fn nacelle_fonts() -> FontDefinitions {
+    let mut fonts = FontDefinitions::default();
+    fonts
+        .font_data
+        .insert("Nacelle".to_owned(), FontData::from_static(NACELLE));
+    fonts.families.insert(
+        FontFamily::Name("Nacelle".into()),
+        vec!["Nacelle".to_owned()],
+    );
+    fonts
+}
+
 #[test]
 fn test_default_theme() {
     let theme = EguiTheme::new(egui::Style::default(), egui::FontDefinitions::default());
@@ -0,0 +1,132 @@
+use egui::{FontDefinitions, FontFamily, FontId, Style, TextStyle};
+
+use super::nacelle_fonts;
+use crate::{
+    dangling_references, families_using_font, move_font, remove_family, remove_font,
+    text_styles_using_family, DanglingReference, EguiTheme, ThemeError, ThemeIssueKind,
+};
+
+/// The family of `nacelle_fonts`, which only lists the Nacelle font.
+fn headings() -> FontFamily {
+    FontFamily::Name("Nacelle".into())
+}
+
+fn theme() -> (Style, FontDefinitions) {
+    let mut fonts = nacelle_fonts();
+    fonts
+        .families
+        .get_mut(&FontFamily::Proportional)
+        .unwrap()
+        .push("Nacelle".to_owned());
+    let mut style = Style::default();
+    style
+        .text_styles
+        .insert(TextStyle::Heading, FontId::new(24.0, headings()));
+    (style, fonts)
+}
+
+#[test]
+fn test_detect_dangling_references() {
+    let (mut style, mut fonts) = theme();
+    assert!(dangling_references(&fonts, &style).is_empty());
+
+    fonts.font_data.remove("Nacelle");
+    fonts.families.remove(&headings());
+    style.override_font_id = Some(FontId::new(12.0, headings()));
+    assert_eq!(
+        dangling_references(&fonts, &style),
+        vec![
+            DanglingReference::Font {
+                family: FontFamily::Proportional,
+                font: "Nacelle".to_owned()
+            },
+            DanglingReference::TextStyle {
+                text_style: TextStyle::Heading,
+                family: headings()
+            },
+            DanglingReference::OverrideFont { family: headings() },
+        ]
+    );
+}
+
+#[test]
+fn test_safe_removal() {
+    let (mut style, mut fonts) = theme();
+    assert_eq!(
+        families_using_font(&fonts, "Nacelle"),
+        vec![FontFamily::Proportional, headings()]
+    );
+    assert_eq!(
+        text_styles_using_family(&style, &headings()),
+        vec![TextStyle::Heading]
+    );
+
+    assert!(matches!(
+        remove_family(&mut fonts, &mut style, &headings(), &headings()),
+        Err(ThemeError::InvalidValue { key, .. }) if key == headings().to_string()
+    ));
+    remove_family(&mut fonts, &mut style, &headings(), &FontFamily::Monospace)
+        .expect("the family exists");
+    assert_eq!(
+        style.text_styles[&TextStyle::Heading].family,
+        FontFamily::Monospace
+    );
+    assert!(remove_family(
+        &mut fonts,
+        &mut style,
+        &FontFamily::Proportional,
+        &FontFamily::Monospace
+    )
+    .is_err());
+
+    assert!(remove_font(&mut fonts, "Nacelle").is_some());
+    assert!(families_using_font(&fonts, "Nacelle").is_empty());
+    assert!(dangling_references(&fonts, &style).is_empty());
+}
+
+#[test]
+fn test_extract_fixes_dangling_references() {
+    let (style, mut fonts) = theme();
+    fonts.families.remove(&headings());
+    let (style, fonts, issues) = EguiTheme::new(style, fonts).extract_with_report();
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert!(matches!(
+        issues[0].kind,
+        ThemeIssueKind::DanglingReference(DanglingReference::TextStyle { .. })
+    ));
+    assert_eq!(
+        style.text_styles[&TextStyle::Heading].family,
+        FontFamily::Proportional
+    );
+    assert!(dangling_references(&fonts, &style).is_empty());
+}
+
+#[test]
+fn test_move_font() {
+    let fonts = || {
+        ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    };
+    let moved = |from, to| {
+        let mut fonts = fonts();
+        move_font(&mut fonts, from, to);
+        fonts.concat()
+    };
+    assert_eq!(moved(0, 0), "abcd");
+    assert_eq!(
+        moved(0, 1),
+        "abcd",
+        "the gap right after a font is where it already is"
+    );
+    assert_eq!(moved(0, 2), "bacd");
+    assert_eq!(moved(0, 4), "bcda");
+    assert_eq!(moved(3, 0), "dabc");
+    assert_eq!(moved(2, 1), "acbd");
+    assert_eq!(
+        moved(1, 10),
+        "acdb",
+        "gaps past the end move the font to the end"
+    );
+}
@@ -0,0 +1,89 @@
+use egui::{FontDefinitions, FontFamily, FontId, Style, TextStyle};
+
+use super::nacelle_fonts;
+use crate::{rename_family, rename_font, ThemeError};
+
+fn fonts() -> FontDefinitions {
+    let mut fonts = nacelle_fonts();
+    fonts
+        .families
+        .get_mut(&FontFamily::Proportional)
+        .unwrap()
+        .insert(1, "Nacelle".to_owned());
+    fonts.families.insert(
+        FontFamily::Name("Headings".into()),
+        vec!["Nacelle".to_owned()],
+    );
+    fonts
+}
+
+#[test]
+fn test_rename_font() {
+    let mut fonts = fonts();
+    let data = fonts.font_data["Nacelle"].clone();
+    rename_font(&mut fonts, "Nacelle", "Nacelle Regular").expect("the font exists");
+    assert!(!fonts.font_data.contains_key("Nacelle"));
+    assert_eq!(fonts.font_data["Nacelle Regular"], data);
+    assert_eq!(
+        fonts.families[&FontFamily::Proportional][1],
+        "Nacelle Regular",
+        "the font keeps its priority"
+    );
+    assert_eq!(
+        fonts.families[&FontFamily::Name("Headings".into())],
+        vec!["Nacelle Regular".to_owned()]
+    );
+
+    assert_eq!(
+        rename_font(&mut fonts, "Nacelle", "Other"),
+        Err(ThemeError::MissingKey("Nacelle".to_owned()))
+    );
+    assert_eq!(
+        rename_font(&mut fonts, "Nacelle", "Nacelle"),
+        Err(ThemeError::MissingKey("Nacelle".to_owned())),
+        "renaming a missing font to itself fails too"
+    );
+    assert_eq!(
+        rename_font(&mut fonts, "Nacelle Regular", "Nacelle Regular"),
+        Ok(())
+    );
+    assert_eq!(
+        rename_font(&mut fonts, "Nacelle Regular", "Hack"),
+        Err(ThemeError::NameTaken("Hack".to_owned()))
+    );
+}
+
+#[test]
+fn test_rename_family() {
+    let mut fonts = fonts();
+    let mut style = Style::default();
+    let headings = FontFamily::Name("Headings".into());
+    style
+        .text_styles
+        .insert(TextStyle::Heading, FontId::new(24.0, headings.clone()));
+    style.override_font_id = Some(FontId::new(14.0, headings.clone()));
+
+    rename_family(&mut fonts, &mut style, "Headings", "Titles").expect("the family exists");
+    let titles = FontFamily::Name("Titles".into());
+    assert!(!fonts.families.contains_key(&headings));
+    assert_eq!(fonts.families[&titles], vec!["Nacelle".to_owned()]);
+    assert_eq!(style.text_styles[&TextStyle::Heading].family, titles);
+    assert_eq!(style.override_font_id.as_ref().unwrap().family, titles);
+    assert_eq!(
+        style.text_styles[&TextStyle::Body].family,
+        FontFamily::Proportional,
+        "other text styles are untouched"
+    );
+
+    fonts
+        .families
+        .insert(FontFamily::Name("Body".into()), Vec::new());
+    assert_eq!(
+        rename_family(&mut fonts, &mut style, "Titles", "Body"),
+        Err(ThemeError::NameTaken("Body".to_owned()))
+    );
+    assert_eq!(
+        rename_family(&mut fonts, &mut style, "Headings", "Headings"),
+        Err(ThemeError::MissingKey("Headings".to_owned()))
+    );
+}
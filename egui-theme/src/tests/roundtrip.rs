@@ -0,0 +1,347 @@
+//! Property tests that generate arbitrary styles and fonts and check that they survive a round trip through the theme unchanged.
+use egui::epaint::Shadow;
+use egui::style::{Margin, WidgetVisuals};
+use egui::{
+    Color32, FontData, FontDefinitions, FontFamily, FontId, FontTweak, Rounding, Stroke, Style,
+    TextStyle, Vec2,
+};
+use proptest::collection::{btree_map, btree_set, vec};
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+use std::sync::OnceLock;
+
+use super::NACELLE;
+use crate::{remove_font, EguiTheme};
+
+/// The number of faces in `collection`.
+const FACES: u32 = 3;
+
+/// A font collection with `FACES` copies of the test font, so fonts can use face indices other than 0.
+fn collection() -> &'static [u8] {
+    static COLLECTION: OnceLock<Vec<u8>> = OnceLock::new();
+    COLLECTION.get_or_init(|| {
+        // Every face shares the table directory of the test font, which directly follows the collection header.
+        let header = 12 + 4 * FACES;
+        let mut collection = b"ttcf".to_vec();
+        collection.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        collection.extend_from_slice(&FACES.to_be_bytes());
+        for _ in 0..FACES {
+            collection.extend_from_slice(&header.to_be_bytes());
+        }
+        let mut font = NACELLE.to_vec();
+        let tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        for table in 0..tables {
+            let record = 12 + 16 * table + 8;
+            let offset = u32::from_be_bytes(font[record..record + 4].try_into().unwrap());
+            font[record..record + 4].copy_from_slice(&(offset + header).to_be_bytes());
+        }
+        collection.extend_from_slice(&font);
+        collection
+    })
+}
+
+fn color() -> impl Strategy<Value = Color32> {
+    any::<[u8; 4]>().prop_map(|[r, g, b, a]| Color32::from_rgba_premultiplied(r, g, b, a))
+}
+
+fn size() -> impl Strategy<Value = f32> {
+    0f32..1000f32
+}
+
+fn vec2() -> impl Strategy<Value = Vec2> {
+    (size(), size()).prop_map(|(x, y)| Vec2::new(x, y))
+}
+
+fn stroke() -> impl Strategy<Value = Stroke> {
+    (size(), color()).prop_map(|(width, color)| Stroke::new(width, color))
+}
+
+fn rounding() -> impl Strategy<Value = Rounding> {
+    (size(), size(), size(), size()).prop_map(|(nw, ne, sw, se)| Rounding { nw, ne, sw, se })
+}
+
+fn margin() -> impl Strategy<Value = Margin> {
+    (size(), size(), size(), size()).prop_map(|(left, right, top, bottom)| Margin {
+        left,
+        right,
+        top,
+        bottom,
+    })
+}
+
+fn shadow() -> impl Strategy<Value = Shadow> {
+    (size(), color()).prop_map(|(extrusion, color)| Shadow { extrusion, color })
+}
+
+fn widget_visuals() -> impl Strategy<Value = WidgetVisuals> {
+    (color(), stroke(), rounding(), stroke(), size()).prop_map(
+        |(bg_fill, bg_stroke, rounding, fg_stroke, expansion)| WidgetVisuals {
+            bg_fill,
+            bg_stroke,
+            rounding,
+            fg_stroke,
+            expansion,
+        },
+    )
+}
+
+fn name() -> impl Strategy<Value = String> {
+    "[a-zA-Z][a-zA-Z0-9 _-]{0,15}"
+}
+
+fn tweak() -> impl Strategy<Value = FontTweak> {
+    (0.5f32..2.0, -1f32..1.0, -10f32..10.0).prop_map(|(scale, y_offset_factor, y_offset)| {
+        FontTweak {
+            scale,
+            y_offset_factor,
+            y_offset,
+        }
+    })
+}
+
+/// Generates the test font on its own or as any face of `collection`, with any tweak.
+fn font_data() -> impl Strategy<Value = FontData> {
+    (proptest::option::of(0..FACES), tweak()).prop_map(|(index, tweak)| {
+        let data = match index {
+            Some(index) => FontData {
+                index,
+                ..FontData::from_static(collection())
+            },
+            None => FontData::from_static(NACELLE),
+        };
+        FontData { tweak, ..data }
+    })
+}
+
+/// Generates font definitions with custom fonts registered under arbitrary names and custom families that use them in any order.
+/// The built-in fonts get arbitrary tweaks and may be removed from the default families or from the definitions altogether.
+fn font_definitions() -> impl Strategy<Value = FontDefinitions> {
+    let defaults = FontDefinitions::default();
+    let builtin_tweaks = vec(proptest::option::of(tweak()), defaults.font_data.len());
+    let builtin_names = defaults.font_data.keys().cloned().collect::<Vec<_>>();
+    let size_of_builtins = builtin_names.len();
+    let removed_builtins = subsequence(builtin_names, 0..=size_of_builtins);
+    let default_chains = defaults
+        .families
+        .into_iter()
+        .map(|(family, chain)| {
+            let size = chain.len();
+            (Just(family), subsequence(chain, 0..=size))
+        })
+        .collect::<Vec<_>>();
+    (
+        btree_map(name(), font_data(), 0..4),
+        btree_set(name(), 0..4),
+        builtin_tweaks,
+        default_chains,
+        removed_builtins,
+    )
+        .prop_flat_map(
+            |(fonts, families, builtin_tweaks, default_chains, removed_builtins)| {
+                let names = fonts.keys().cloned().collect::<Vec<_>>();
+                let size = names.len();
+                let chains = vec(subsequence(names, 0..=size).prop_shuffle(), families.len());
+                (
+                    Just(fonts),
+                    Just(families),
+                    Just(builtin_tweaks),
+                    Just(default_chains),
+                    Just(removed_builtins),
+                    chains,
+                )
+            },
+        )
+        .prop_map(
+            |(fonts, families, builtin_tweaks, default_chains, removed_builtins, chains)| {
+                let mut font_definitions = FontDefinitions::default();
+                for (data, tweak) in font_definitions.font_data.values_mut().zip(builtin_tweaks) {
+                    if let Some(tweak) = tweak {
+                        data.tweak = tweak;
+                    }
+                }
+                font_definitions.families.extend(default_chains);
+                for name in removed_builtins {
+                    remove_font(&mut font_definitions, &name);
+                }
+                font_definitions.font_data.extend(fonts);
+                for (family, chain) in families.into_iter().zip(chains) {
+                    font_definitions
+                        .families
+                        .insert(FontFamily::Name(family.into()), chain);
+                }
+                font_definitions
+            },
+        )
+}
+
+/// Generates a style whose text styles and override font reference the families of `fonts`.
+/// Any of the default text styles may be removed.
+fn style(fonts: &FontDefinitions) -> impl Strategy<Value = Style> {
+    let families = fonts.families.keys().cloned().collect::<Vec<_>>();
+    let default_text_styles = Style::default().text_styles.into_keys().collect::<Vec<_>>();
+    let size_of_defaults = default_text_styles.len();
+    let text_styles = (
+        vec((name(), size(), select(families.clone())), 0..4),
+        subsequence(default_text_styles.clone(), 0..=size_of_defaults),
+        proptest::option::of(select(default_text_styles)),
+        proptest::option::of((size(), select(families))),
+    );
+    let visuals = (
+        any::<bool>(),
+        proptest::option::of(color()),
+        [
+            widget_visuals(),
+            widget_visuals(),
+            widget_visuals(),
+            widget_visuals(),
+            widget_visuals(),
+        ],
+        (color(), stroke()),
+        [color(), color(), color(), color()],
+        rounding(),
+        [shadow(), shadow()],
+        [size(), size(), size()],
+        [any::<bool>(), any::<bool>(), any::<bool>()],
+    );
+    let spacing = (
+        [vec2(), vec2(), vec2()],
+        margin(),
+        [size(), size(), size(), size(), size()],
+        [size(), size(), size(), size()],
+        any::<bool>(),
+    );
+    let general = (
+        proptest::option::of(any::<bool>()),
+        size(),
+        any::<bool>(),
+        [size(), size()],
+        any::<bool>(),
+        [any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()],
+    );
+    (text_styles, visuals, spacing, general).prop_map(|(text_styles, visuals, spacing, general)| {
+        let mut style = Style::default();
+        let (text_styles, removed, override_text_style, override_font_id) = text_styles;
+        for text_style in removed {
+            style.text_styles.remove(&text_style);
+        }
+        for (name, size, family) in text_styles {
+            style
+                .text_styles
+                .insert(TextStyle::Name(name.into()), FontId::new(size, family));
+        }
+        style.override_text_style = override_text_style;
+        style.override_font_id = override_font_id.map(|(size, family)| FontId::new(size, family));
+
+        let (
+            dark_mode,
+            override_text_color,
+            [noninteractive, inactive, hovered, active, open],
+            (selection_fill, selection_stroke),
+            [hyperlink, faint_bg, extreme_bg, code_bg],
+            window_rounding,
+            [window_shadow, popup_shadow],
+            [resize_corner_size, text_cursor_width, clip_rect_margin],
+            [text_cursor_preview, button_frame, collapsing_header_frame],
+        ) = visuals;
+        let v = &mut style.visuals;
+        v.dark_mode = dark_mode;
+        v.override_text_color = override_text_color;
+        v.widgets.noninteractive = noninteractive;
+        v.widgets.inactive = inactive;
+        v.widgets.hovered = hovered;
+        v.widgets.active = active;
+        v.widgets.open = open;
+        v.selection.bg_fill = selection_fill;
+        v.selection.stroke = selection_stroke;
+        v.hyperlink_color = hyperlink;
+        v.faint_bg_color = faint_bg;
+        v.extreme_bg_color = extreme_bg;
+        v.code_bg_color = code_bg;
+        v.window_rounding = window_rounding;
+        v.window_shadow = window_shadow;
+        v.popup_shadow = popup_shadow;
+        v.resize_corner_size = resize_corner_size;
+        v.text_cursor_width = text_cursor_width;
+        v.clip_rect_margin = clip_rect_margin;
+        v.text_cursor_preview = text_cursor_preview;
+        v.button_frame = button_frame;
+        v.collapsing_header_frame = collapsing_header_frame;
+
+        let (
+            [item_spacing, button_padding, interact_size],
+            window_margin,
+            [indent, slider_width, text_edit_width, icon_width, icon_width_inner],
+            [icon_spacing, tooltip_width, combo_height, scroll_bar_width],
+            indent_ends_with_horizontal_line,
+        ) = spacing;
+        let s = &mut style.spacing;
+        s.item_spacing = item_spacing;
+        s.button_padding = button_padding;
+        s.interact_size = interact_size;
+        s.window_margin = window_margin;
+        s.indent = indent;
+        s.slider_width = slider_width;
+        s.text_edit_width = text_edit_width;
+        s.icon_width = icon_width;
+        s.icon_width_inner = icon_width_inner;
+        s.icon_spacing = icon_spacing;
+        s.tooltip_width = tooltip_width;
+        s.combo_height = combo_height;
+        s.scroll_bar_width = scroll_bar_width;
+        s.indent_ends_with_horizontal_line = indent_ends_with_horizontal_line;
+
+        let (
+            wrap,
+            animation_time,
+            explanation_tooltips,
+            [resize_grab_radius_side, resize_grab_radius_corner],
+            show_tooltips_only_when_still,
+            [debug_on_hover, show_expand_width, show_expand_height, show_resize],
+        ) = general;
+        style.wrap = wrap;
+        style.animation_time = animation_time;
+        style.explanation_tooltips = explanation_tooltips;
+        style.interaction.resize_grab_radius_side = resize_grab_radius_side;
+        style.interaction.resize_grab_radius_corner = resize_grab_radius_corner;
+        style.interaction.show_tooltips_only_when_still = show_tooltips_only_when_still;
+        style.debug.debug_on_hover = debug_on_hover;
+        style.debug.show_expand_width = show_expand_width;
+        style.debug.show_expand_height = show_expand_height;
+        style.debug.show_resize = show_resize;
+        style
+    })
+}
+
+fn theme() -> impl Strategy<Value = (Style, FontDefinitions)> {
+    font_definitions().prop_flat_map(|fonts| (style(&fonts), Just(fonts)))
+}
+
+proptest! {
+    // Every case serializes the embedded test font so keep the number of cases modest.
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_ron_round_trip((style, fonts) in theme()) {
+        let theme = EguiTheme::new(style.clone(), fonts.clone());
+        let serialized = ron::to_string(&theme).expect("serialization failed");
+        let deserialized = ron::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+        let (de_style, de_fonts) = deserialized
+            .extract_strict()
+            .expect("the theme should load in strict mode");
+        prop_assert_eq!(de_style, style);
+        prop_assert_eq!(de_fonts, fonts);
+    }
+
+    #[test]
+    fn test_json_round_trip((style, fonts) in theme()) {
+        let theme = EguiTheme::new(style.clone(), fonts.clone());
+        let serialized = serde_json::to_string(&theme).expect("serialization failed");
+        let deserialized =
+            serde_json::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+        let (de_style, de_fonts) = deserialized
+            .extract_strict()
+            .expect("the theme should load in strict mode");
+        prop_assert_eq!(de_style, style);
+        prop_assert_eq!(de_fonts, fonts);
+    }
+}
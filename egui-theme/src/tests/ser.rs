@@ -35,18 +35,18 @@ fn test_default_theme_extract() {
         "style.animation time is not default"
     );
 
-    for name in crate::DEFAULT_FONTS.iter() {
+    for name in crate::builtin_font_names().iter() {
         assert!(
-            fonts.font_data.contains_key(*name),
+            fonts.font_data.contains_key(name),
             "font doesn't property have the data for `{name}`."
         );
         let default_font = default_fonts
             .font_data
-            .get(*name)
+            .get(name)
             .expect("{name} does not exist");
         let serialized_font = default_fonts
             .font_data
-            .get(*name)
+            .get(name)
             .expect("{name} does not exist");
         assert_eq!(default_font, serialized_font);
     }
@@ -70,11 +70,11 @@ fn test_custom_font_extract() {
 
     let (_, fonts) = theme.extract();
     assert!(
-        fonts.font_data.contains_key(&FONT_NAME.to_owned()),
+        fonts.font_data.contains_key(FONT_NAME),
         "does not have `Nacelle` key"
     );
     assert_eq!(
-        fonts.font_data.get(&FONT_NAME.to_owned()).unwrap(),
+        fonts.font_data.get(FONT_NAME).unwrap(),
         &FontData::from_static(include_bytes!("test-fonts/Nacelle-Regular.otf")),
         "font data does not match"
     );
@@ -204,8 +204,7 @@ fn test_text_style() {
     assert!(
         de_style
             .text_styles
-            .get(&TextStyle::Name("NacelleStyle".into()))
-            .is_some(),
+            .contains_key(&TextStyle::Name("NacelleStyle".into())),
         "could not get the text_style"
     );
     assert_eq!(
@@ -221,7 +220,7 @@ fn test_text_style() {
 fn test_colors() {
     let mut style = Style::default();
     let fg_stroke = egui::Stroke::new(1f32, egui::Color32::TRANSPARENT);
-    style.visuals.widgets.noninteractive.fg_stroke = fg_stroke.clone();
+    style.visuals.widgets.noninteractive.fg_stroke = fg_stroke;
     style.visuals.widgets.inactive.bg_fill = egui::Color32::LIGHT_RED;
 
     let theme = EguiTheme::new(style, FontDefinitions::default());
@@ -231,8 +230,7 @@ fn test_colors() {
     let (de_style, _fonts) = deserialized.extract();
 
     assert_eq!(
-        de_style.visuals.widgets.noninteractive.fg_stroke,
-        fg_stroke.clone(),
+        de_style.visuals.widgets.noninteractive.fg_stroke, fg_stroke,
         "stroke doesn't match"
     );
     assert_eq!(
@@ -241,3 +239,46 @@ fn test_colors() {
         "Color doesn't match"
     );
 }
+
+/// Fails whenever egui adds a field to `Style` that the theme does not store.
+#[test]
+fn test_style_field_coverage() {
+    const NESTED: [&str; 3] = ["spacing", "interaction", "visuals"];
+    let theme = EguiTheme::new(Style::default(), FontDefinitions::default());
+    let style = serde_json::to_value(Style::default()).expect("serialization failed");
+    let style = style.as_object().expect("style should be an object");
+    for (field, value) in style.iter() {
+        if NESTED.contains(&field.as_str()) {
+            let sub_fields = value.as_object().expect("nested style should be an object");
+            for sub_field in sub_fields.keys() {
+                let key = format!("{field}.{sub_field}");
+                assert!(
+                    theme.style.contains_key(&key),
+                    "`{key}` is not stored in the theme"
+                );
+            }
+        } else {
+            assert!(
+                theme.style.contains_key(field),
+                "`{field}` is not stored in the theme"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_debug_and_spacing_round_trip() {
+    let mut style = Style::default();
+    style.debug.debug_on_hover = true;
+    style.debug.show_resize = true;
+    style.spacing.icon_width_inner = 3.0;
+
+    let theme = EguiTheme::new(style.clone(), FontDefinitions::default());
+    let serialized = serde_json::to_string(&theme).expect("serialization failed");
+    let deserialized =
+        serde_json::from_str::<EguiTheme>(serialized.as_str()).expect("deserialization failed");
+    let (de_style, _fonts) = deserialized
+        .extract_strict()
+        .expect("the theme should load in strict mode");
+    assert_eq!(de_style, style, "style should match");
+}
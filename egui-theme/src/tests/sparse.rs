@@ -0,0 +1,52 @@
+use egui::{Color32, FontDefinitions, Style, Visuals};
+
+use crate::EguiTheme;
+
+#[test]
+fn test_sparse_default() {
+    let theme = EguiTheme::new_sparse(
+        Style::default(),
+        FontDefinitions::default(),
+        &Style::default(),
+    );
+    assert!(theme.style.is_empty(), "no style values should be stored");
+    assert!(theme.fonts.is_empty(), "no font values should be stored");
+    let (style, fonts) = theme
+        .with_baseline(&Style::default())
+        .extract_strict()
+        .expect("the baseline should fill every key");
+    assert_eq!(style, Style::default(), "default should match");
+    assert_eq!(fonts, FontDefinitions::default(), "default should match");
+}
+
+#[test]
+fn test_sparse_nested_fields() {
+    let baseline = Style {
+        visuals: Visuals::light(),
+        ..Style::default()
+    };
+    let mut style = baseline.clone();
+    style.visuals.widgets.hovered.bg_fill = Color32::RED;
+    style.spacing.item_spacing.x = 12.0;
+
+    let theme = EguiTheme::new_sparse(style.clone(), FontDefinitions::default(), &baseline);
+    assert_eq!(theme.style.len(), 2, "only two keys should differ");
+    assert_eq!(
+        theme.style.get("visuals.widgets"),
+        Some(&serde_json::json!({ "hovered": { "bg_fill": [255, 0, 0, 255] } })),
+        "only the changed widget field should be stored"
+    );
+    assert_eq!(
+        theme.style.get("spacing.item_spacing"),
+        Some(&serde_json::json!({ "x": 12.0 })),
+        "only the changed vector component should be stored"
+    );
+
+    let serialized = ron::to_string(&theme).expect("serialization failed");
+    let deserialized = ron::from_str::<EguiTheme>(&serialized).expect("failed to deserialize");
+    let (de_style, _) = deserialized
+        .with_baseline(&baseline)
+        .extract_strict()
+        .expect("the baseline should fill every key");
+    assert_eq!(de_style, style, "style should match");
+}
@@ -0,0 +1,49 @@
+use egui::{FontDefinitions, FontFamily};
+
+use super::{nacelle_fonts, NACELLE};
+use crate::{latin1_chars, missing_glyphs, subset_font, subset_fonts, validate_font, EguiTheme};
+
+#[test]
+fn test_subset_font_keeps_requested_glyphs() {
+    let subset = subset_font(NACELLE, 0, "Hello".chars()).expect("the font can be subset");
+    assert!(subset.len() < NACELLE.len(), "the subset should be smaller");
+    validate_font(&subset, 0).expect("the subset should still be usable by egui");
+
+    let original = ttf_parser::Face::parse(NACELLE, 0).unwrap();
+    let face = ttf_parser::Face::parse(&subset, 0).expect("the subset should parse");
+    for c in "Helo".chars() {
+        assert_eq!(
+            face.glyph_index(c),
+            original.glyph_index(c),
+            "glyph ids are kept"
+        );
+    }
+    assert_eq!(
+        face.glyph_index('x'),
+        None,
+        "other characters should fall back to the next font"
+    );
+}
+
+#[test]
+fn test_subset_fonts_before_export() {
+    let chars = latin1_chars().chain("→".chars()).collect::<Vec<_>>();
+    let subset = subset_fonts(nacelle_fonts(), chars).expect("the fonts can be subset");
+    assert!(subset.font_data["Nacelle"].font.len() < NACELLE.len());
+    assert_eq!(
+        subset.font_data["Hack"].font,
+        FontDefinitions::default().font_data["Hack"].font,
+        "the default fonts are not written to the theme and left as is"
+    );
+
+    let theme = EguiTheme::new(egui::Style::default(), subset);
+    let (_, fonts, issues) = EguiTheme::from_str_as(
+        &theme.to_string_as(crate::ThemeFormat::Json).unwrap(),
+        crate::ThemeFormat::Json,
+    )
+    .unwrap()
+    .extract_with_report();
+    assert!(issues.is_empty(), "{issues:?}");
+    let missing = missing_glyphs(&fonts, crate::UNICODE_RANGES[0].chars());
+    assert!(missing[&FontFamily::Name("Nacelle".into())].is_empty());
+}
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use super::NACELLE;
+use crate::scan_font_dirs;
+use crate::theme::system_fonts::fontconfig_dirs;
+
+#[test]
+fn test_scan_font_dirs() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/test-fonts");
+    // Scanning a directory twice, or a directory and its parent, finds every font once.
+    let fonts = scan_font_dirs([dir.clone(), dir.clone(), dir.join("..")]);
+    let font = fonts
+        .iter()
+        .find(|font| font.path.ends_with("test-fonts/Nacelle-Regular.otf"))
+        .expect("the test font should be found");
+    assert_eq!(
+        fonts
+            .iter()
+            .filter(|font| font.family_name == "Nacelle")
+            .count(),
+        1,
+        "every font file should be found once"
+    );
+    assert!(
+        fonts
+            .iter()
+            .all(|font| font.path.extension().unwrap() == "otf"),
+        "only font files should be read"
+    );
+    assert_eq!(font.index, 0);
+    assert_eq!(font.name(), "Nacelle");
+
+    let data = font.load().expect("the test font should load");
+    assert_eq!(data.font.as_ref(), NACELLE);
+    assert!(
+        scan_font_dirs([dir.join("missing")]).is_empty(),
+        "missing directories should be skipped"
+    );
+}
+
+#[test]
+fn test_fontconfig_dirs() {
+    let conf = r#"<?xml version="1.0"?>
+<fontconfig>
+    <dir>/usr/share/fonts</dir>
+    <dir prefix="xdg">fonts</dir>
+    <!-- <dir>/opt/commented/fonts</dir> -->
+    <dir>~/.fonts</dir>
+    <dir prefix="relative">local-fonts</dir>
+    <dir>not/absolute</dir>
+    <cachedir>/var/cache/fontconfig</cachedir>
+</fontconfig>"#;
+    let dirs = fontconfig_dirs(
+        conf,
+        Some(Path::new("/etc/fonts")),
+        Some(Path::new("/home/user")),
+        Some(Path::new("/home/user/.local/share")),
+    );
+    assert_eq!(
+        dirs,
+        [
+            "/usr/share/fonts",
+            "/home/user/.local/share/fonts",
+            "/home/user/.fonts",
+            "/etc/fonts/local-fonts",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>()
+    );
+    assert!(
+        fontconfig_dirs(conf, None, None, None)
+            .iter()
+            .all(|dir| dir.starts_with("/usr")),
+        "prefixed entries cannot be resolved without their base directories"
+    );
+}
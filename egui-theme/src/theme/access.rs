@@ -0,0 +1,102 @@
+use egui::Style;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{style, EguiTheme, ThemeError, ThemeValue};
+
+/// Typed access to individual `style` keys without extracting the whole theme.
+///
+/// Keys are the dotted names stored in the theme such as `visuals.hyperlink_color`. Fields nested inside a key can be addressed by continuing the path, e.g. `visuals.widgets.hovered.bg_fill`.
+impl EguiTheme {
+    /// Returns every `style` key stored in the theme in sorted order.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys = self.style.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Returns true if the theme stores a value for `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.value(key).is_some()
+    }
+
+    /// Decodes the value stored for `key`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T, ThemeError> {
+        let value = self
+            .value(key)
+            .ok_or_else(|| ThemeError::MissingKey(key.to_owned()))?;
+        serde_json::from_value(value.to_owned()).map_err(|error| ThemeError::InvalidValue {
+            key: key.to_owned(),
+            error: error.to_string(),
+        })
+    }
+
+    /// Stores `value` for `key`, replacing any previous value.
+    ///
+    /// The value is not checked against the type egui expects, use `extract_strict` to validate the theme afterwards.
+    /// Fails if `key` is nested inside a value that is not an object, including a null value.
+    pub fn set<T: Serialize>(&mut self, key: &str, value: T) -> Result<(), ThemeError> {
+        let invalid = |error: String| ThemeError::InvalidValue {
+            key: key.to_owned(),
+            error,
+        };
+        let value = serde_json::to_value(value).map_err(|error| invalid(error.to_string()))?;
+        let (top, path) = self.split_key(key);
+        let mut target = self
+            .style
+            .entry(top)
+            .or_insert_with(|| ThemeValue::Object(Default::default()));
+        for field in path {
+            // An unset `Option` such as `visuals.override_text_color` has no fields to set, the whole value has to be set instead.
+            target = target
+                .as_object_mut()
+                .ok_or_else(|| invalid(format!("`{field}` is not inside an object")))?
+                .entry(field)
+                .or_insert_with(|| ThemeValue::Object(Default::default()));
+        }
+        *target = value;
+        Ok(())
+    }
+
+    /// Removes the value stored for `key`, returning it if it existed.
+    /// When the theme has a base, the removed value will be inherited from it instead.
+    pub fn remove(&mut self, key: &str) -> Option<ThemeValue> {
+        let (top, path) = self.split_key(key);
+        let (last, parents) = match path.split_last() {
+            Some((last, parents)) => (last, parents),
+            None => return self.style.remove(&top),
+        };
+        let mut target = self.style.get_mut(&top)?;
+        for field in parents {
+            target = target.get_mut(field)?;
+        }
+        target.as_object_mut()?.remove(*last)
+    }
+
+    fn value(&self, key: &str) -> Option<&ThemeValue> {
+        let (top, path) = self.split_key(key);
+        let mut value = self.style.get(&top)?;
+        for field in path {
+            value = value.get(field)?;
+        }
+        Some(value)
+    }
+
+    /// Splits `key` into the stored key and the path of fields nested inside of it.
+    /// Keys that are not stored yet are matched against the keys egui-theme knows about.
+    fn split_key<'a>(&self, key: &'a str) -> (String, Vec<&'a str>) {
+        let parts = key.split('.').collect::<Vec<_>>();
+        let find = |contains: &dyn Fn(&str) -> bool| {
+            (1..=parts.len())
+                .rev()
+                .map(|i| (parts[..i].join("."), i))
+                .find(|(top, _)| contains(top))
+                .map(|(top, i)| (top, parts[i..].to_vec()))
+        };
+        find(&|top| self.style.contains_key(top))
+            .or_else(|| {
+                let known = style::from_style(Style::default());
+                find(&|top| known.contains_key(top))
+            })
+            .unwrap_or_else(|| (key.to_owned(), Vec::new()))
+    }
+}
@@ -0,0 +1,212 @@
+use egui::{FontDefinitions, Style, Visuals};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::fonts::{
+    FAMILIES_KEY, FONT_ALIASES_KEY, FONT_DATA_KEY, FONT_HASHES_KEY, FONT_REFERENCES_KEY,
+    FONT_TWEAKS_KEY, REMOVED_FONTS_KEY,
+};
+use super::{fonts, style, EguiTheme, ThemeError, ThemeValue};
+
+/// The font keys that together describe which fonts exist and how the families use them.
+/// They are replaced as a whole, merging them key by key would bring back fonts and families a child theme removed.
+pub(crate) const FONT_SET_KEYS: &[&str] = &[
+    FONT_DATA_KEY,
+    FONT_HASHES_KEY,
+    FONT_ALIASES_KEY,
+    FONT_REFERENCES_KEY,
+    FONT_TWEAKS_KEY,
+    REMOVED_FONTS_KEY,
+    FAMILIES_KEY,
+];
+
+/// The parent that an `EguiTheme` inherits any keys it does not store itself from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeBase {
+    /// egui's built-in dark style with the default fonts.
+    Dark,
+    /// egui's built-in light style with the default fonts.
+    Light,
+    /// Another theme identified by name, such as a relative file path. This is loaded through a `ThemeResolver`.
+    Theme(String),
+}
+
+/// Loads the parent themes referenced by `ThemeBase::Theme`.
+///
+/// This is implemented for any `Fn(&str) -> Result<EguiTheme, ThemeError>` so a closure that reads the theme from disk is usually sufficient.
+pub trait ThemeResolver {
+    fn resolve(&self, name: &str) -> Result<EguiTheme, ThemeError>;
+}
+
+impl<F> ThemeResolver for F
+where
+    F: Fn(&str) -> Result<EguiTheme, ThemeError>,
+{
+    fn resolve(&self, name: &str) -> Result<EguiTheme, ThemeError> {
+        self(name)
+    }
+}
+
+/// Produces the full key maps for one of the built-in bases.
+fn builtin_maps(visuals: Visuals) -> (HashMap<String, ThemeValue>, HashMap<String, ThemeValue>) {
+    let style = Style {
+        visuals,
+        ..Style::default()
+    };
+    (
+        style::from_style(style),
+        fonts::from_fonts(FontDefinitions::default()),
+    )
+}
+
+/// Overlays the `child` keys on top of the `parent` keys.
+/// Objects are merged field by field so a child only needs to store the fields that differ.
+pub(crate) fn merge(
+    mut parent: HashMap<String, ThemeValue>,
+    child: HashMap<String, ThemeValue>,
+) -> HashMap<String, ThemeValue> {
+    for (key, value) in child {
+        match parent.get_mut(&key) {
+            Some(parent_value) => merge_value(parent_value, value),
+            None => {
+                parent.insert(key, value);
+            }
+        }
+    }
+    parent
+}
+
+/// Overlays the `child` font keys on top of the `parent` font keys.
+/// If the child stores its own `font_data`, none of the parent's `FONT_SET_KEYS` are inherited.
+pub(crate) fn merge_fonts(
+    mut parent: HashMap<String, ThemeValue>,
+    child: HashMap<String, ThemeValue>,
+) -> HashMap<String, ThemeValue> {
+    if child.contains_key(FONT_DATA_KEY) {
+        parent.retain(|key, _| !FONT_SET_KEYS.contains(&key.as_str()));
+    }
+    merge(parent, child)
+}
+
+fn merge_value(parent: &mut ThemeValue, child: ThemeValue) {
+    match (parent, child) {
+        (ThemeValue::Object(parent), ThemeValue::Object(child)) => {
+            for (key, value) in child {
+                match parent.get_mut(&key) {
+                    Some(parent_value) => merge_value(parent_value, value),
+                    None => {
+                        parent.insert(key, value);
+                    }
+                }
+            }
+        }
+        (parent, child) => *parent = child,
+    }
+}
+
+/// Merges a theme with one of the built-in bases. Themes without a base or with a `ThemeBase::Theme` base are returned untouched.
+pub(crate) fn merge_builtin(mut theme: EguiTheme) -> EguiTheme {
+    let visuals = match theme.base {
+        Some(ThemeBase::Dark) => Visuals::dark(),
+        Some(ThemeBase::Light) => Visuals::light(),
+        _ => return theme,
+    };
+    let (style, fonts) = builtin_maps(visuals);
+    theme.style = merge(style, theme.style);
+    theme.fonts = merge_fonts(fonts, theme.fonts);
+    theme.base = None;
+    theme
+}
+
+/// Recursively merges the theme with all of its parents, producing a theme without a base.
+pub(crate) fn resolve(
+    theme: EguiTheme,
+    resolver: &dyn ThemeResolver,
+    visited: &mut Vec<String>,
+) -> Result<EguiTheme, ThemeError> {
+    let name = match &theme.base {
+        Some(ThemeBase::Theme(name)) => name.to_owned(),
+        _ => return Ok(merge_builtin(theme)),
+    };
+    if visited.contains(&name) {
+        return Err(ThemeError::BaseCycle(name));
+    }
+    visited.push(name.clone());
+    let parent = resolve(resolver.resolve(&name)?, resolver, visited)?;
+    Ok(EguiTheme {
+        style: merge(parent.style, theme.style),
+        fonts: merge_fonts(parent.fonts, theme.fonts),
+        base: None,
+        ..theme
+    })
+}
+
+/// Produces the fully merged parent theme that `base` refers to.
+pub(crate) fn resolve_parent(
+    base: ThemeBase,
+    resolver: &dyn ThemeResolver,
+) -> Result<EguiTheme, ThemeError> {
+    let theme = EguiTheme {
+        egui_theme_version: crate::EGUI_THEME_VERSION.to_owned(),
+        egui_version: crate::EGUI_VERSION.to_owned(),
+        style: HashMap::new(),
+        fonts: HashMap::new(),
+        base: Some(base),
+    };
+    resolve(theme, resolver, &mut Vec::new())
+}
+
+/// Removes every key from `child` that has the same value in `parent`.
+/// Objects are compared field by field so only the fields that differ are kept.
+pub(crate) fn strip(
+    child: HashMap<String, ThemeValue>,
+    parent: &HashMap<String, ThemeValue>,
+) -> HashMap<String, ThemeValue> {
+    child
+        .into_iter()
+        .filter_map(|(key, value)| match parent.get(&key) {
+            Some(parent_value) => strip_value(value, parent_value).map(|value| (key, value)),
+            None => Some((key, value)),
+        })
+        .collect()
+}
+
+/// Removes every font key from `child` that has the same value in `parent`.
+/// Unless all of the `FONT_SET_KEYS` are the same, they are kept as a whole so `merge_fonts` does not inherit any of them.
+pub(crate) fn strip_fonts(
+    child: HashMap<String, ThemeValue>,
+    parent: &HashMap<String, ThemeValue>,
+) -> HashMap<String, ThemeValue> {
+    let same_fonts = FONT_SET_KEYS
+        .iter()
+        .all(|key| child.get(*key) == parent.get(*key));
+    if same_fonts {
+        return strip(child, parent);
+    }
+    let (font_set, rest): (HashMap<_, _>, HashMap<_, _>) = child
+        .into_iter()
+        .partition(|(key, _)| FONT_SET_KEYS.contains(&key.as_str()));
+    let mut stripped = strip(rest, parent);
+    stripped.extend(font_set);
+    stripped
+}
+
+fn strip_value(child: ThemeValue, parent: &ThemeValue) -> Option<ThemeValue> {
+    if &child == parent {
+        return None;
+    }
+    match (child, parent) {
+        (ThemeValue::Object(child), ThemeValue::Object(parent)) => Some(ThemeValue::Object(
+            child
+                .into_iter()
+                .filter_map(|(key, value)| match parent.get(&key) {
+                    Some(parent_value) => {
+                        strip_value(value, parent_value).map(|value| (key, value))
+                    }
+                    None => Some((key, value)),
+                })
+                .collect(),
+        )),
+        (child, _) => Some(child),
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{EguiTheme, ThemeBase, ThemeError, ThemeValue};
+
+/// Identifies a binary theme. Followed by the container version and the compression byte.
+const MAGIC: &[u8; 4] = b"EGTB";
+const CONTAINER_VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 2;
+/// The largest payload a compressed theme may inflate to, so a small crafted file cannot exhaust the memory.
+/// This leaves plenty of room for themes embedding several CJK fonts.
+const MAX_PAYLOAD_LEN: usize = 256 * 1024 * 1024;
+
+/// How the payload of a binary theme is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeCompression {
+    /// The payload is stored as is, which is faster to load but larger.
+    None,
+    /// The payload is compressed with DEFLATE.
+    #[default]
+    Deflate,
+}
+
+impl ThemeCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            ThemeCompression::None => 0,
+            ThemeCompression::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(ThemeCompression::None),
+            1 => Some(ThemeCompression::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// The contents of a binary theme.
+///
+/// The style and fonts are kept as compact JSON so that the binary encoding loads exactly like the text formats,
+/// but the font bytes are moved out of the JSON arrays into raw byte buffers as they make up the bulk of a theme.
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    egui_theme_version: String,
+    egui_version: String,
+    base: Option<ThemeBase>,
+    style: String,
+    fonts: String,
+    font_bytes: Vec<(String, Vec<u8>)>,
+}
+
+/// Moves the `font` byte arrays out of every entry in `font_data`.
+fn take_font_bytes(fonts: &mut HashMap<String, ThemeValue>) -> Vec<(String, Vec<u8>)> {
+    let font_data = match fonts.get_mut("font_data").and_then(|v| v.as_object_mut()) {
+        Some(font_data) => font_data,
+        None => return Vec::new(),
+    };
+    let mut font_bytes = Vec::new();
+    for (name, data) in font_data.iter_mut() {
+        let font = match data.get_mut("font") {
+            Some(font) => font,
+            None => continue,
+        };
+        // Anything that isn't a valid byte array is left in place and reported when extracting.
+        let bytes = font.as_array().and_then(|values| {
+            values
+                .iter()
+                .map(|value| value.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect::<Option<Vec<_>>>()
+        });
+        if let Some(bytes) = bytes {
+            *font = ThemeValue::Null;
+            font_bytes.push((name.to_owned(), bytes));
+        }
+    }
+    font_bytes
+}
+
+fn restore_font_bytes(fonts: &mut HashMap<String, ThemeValue>, font_bytes: Vec<(String, Vec<u8>)>) {
+    let font_data = match fonts.get_mut("font_data").and_then(|v| v.as_object_mut()) {
+        Some(font_data) => font_data,
+        None => return,
+    };
+    for (name, bytes) in font_bytes {
+        if let Some(ThemeValue::Object(data)) = font_data.get_mut(&name) {
+            data.insert("font".to_owned(), ThemeValue::from(bytes));
+        }
+    }
+}
+
+fn invalid(error: impl ToString) -> ThemeError {
+    ThemeError::InvalidBinary(error.to_string())
+}
+
+/// Inflates a DEFLATE compressed payload, failing if it is larger than `limit` bytes.
+pub(crate) fn decompress(payload: &[u8], limit: usize) -> Result<Vec<u8>, ThemeError> {
+    miniz_oxide::inflate::decompress_to_vec_with_limit(payload, limit).map_err(|error| match error
+        .status
+    {
+        miniz_oxide::inflate::TINFLStatus::HasMoreOutput => {
+            invalid(format!("the payload is larger than {limit} bytes"))
+        }
+        _ => invalid(format!("decompression failed: {error}")),
+    })
+}
+
+impl EguiTheme {
+    /// Encodes the theme into a compact, versioned binary container.
+    ///
+    /// This is intended for shipping themes inside release builds, e.g. with `include_bytes!`. Use one of the text formats for themes that are edited by hand.
+    pub fn to_bytes(&self, compression: ThemeCompression) -> Result<Vec<u8>, ThemeError> {
+        let mut fonts = self.fonts.clone();
+        let font_bytes = take_font_bytes(&mut fonts);
+        let payload = Payload {
+            egui_theme_version: self.egui_theme_version.clone(),
+            egui_version: self.egui_version.clone(),
+            base: self.base.clone(),
+            style: serde_json::to_string(&self.style).map_err(invalid)?,
+            fonts: serde_json::to_string(&fonts).map_err(invalid)?,
+            font_bytes,
+        };
+        let payload = bincode::serialize(&payload).map_err(invalid)?;
+        let payload = match compression {
+            ThemeCompression::None => payload,
+            ThemeCompression::Deflate => miniz_oxide::deflate::compress_to_vec(&payload, 9),
+        };
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(CONTAINER_VERSION);
+        bytes.push(compression.to_byte());
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    /// Decodes a theme created with `to_bytes`.
+    ///
+    /// ```ignore
+    /// let theme = EguiTheme::from_bytes(include_bytes!("theme.eguitheme.bin"))?;
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ThemeError> {
+        if !Self::is_binary(bytes) {
+            return Err(invalid("missing the binary theme header"));
+        }
+        let version = bytes[MAGIC.len()];
+        if version != CONTAINER_VERSION {
+            return Err(invalid(format!("unsupported container version {version}")));
+        }
+        let compression = ThemeCompression::from_byte(bytes[MAGIC.len() + 1])
+            .ok_or_else(|| invalid("unknown compression"))?;
+        let payload = &bytes[HEADER_LEN..];
+        let payload = match compression {
+            ThemeCompression::None => bincode::deserialize::<Payload>(payload),
+            ThemeCompression::Deflate => {
+                let payload = decompress(payload, MAX_PAYLOAD_LEN)?;
+                bincode::deserialize::<Payload>(&payload)
+            }
+        }
+        .map_err(invalid)?;
+
+        let style = serde_json::from_str(&payload.style).map_err(invalid)?;
+        let mut fonts = serde_json::from_str(&payload.fonts).map_err(invalid)?;
+        restore_font_bytes(&mut fonts, payload.font_bytes);
+        Ok(Self {
+            egui_theme_version: payload.egui_theme_version,
+            egui_version: payload.egui_version,
+            style,
+            fonts,
+            base: payload.base,
+        })
+    }
+
+    /// Returns true if `bytes` starts with the header written by `to_bytes`.
+    pub fn is_binary(bytes: &[u8]) -> bool {
+        bytes.len() >= HEADER_LEN && bytes.starts_with(MAGIC)
+    }
+}
@@ -0,0 +1,48 @@
+use egui::{FontDefinitions, FontFamily};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::font_info::parse;
+
+/// Finds the characters of `chars` that no font in each family's fallback chain has a glyph for.
+///
+/// Every family of `fonts` is returned, families that can render every character map to an empty list. Control characters are ignored, and fonts that are missing or cannot be parsed are treated as having no glyphs.
+///
+/// ```ignore
+/// let missing = missing_glyphs(&fonts, "Привет, 世界".chars());
+/// let missing = missing_glyphs(&fonts, UNICODE_RANGES[0].chars());
+/// ```
+pub fn missing_glyphs(
+    fonts: &FontDefinitions,
+    chars: impl IntoIterator<Item = char>,
+) -> BTreeMap<FontFamily, Vec<char>> {
+    let chars = chars
+        .into_iter()
+        .filter(|c| !c.is_control())
+        .collect::<BTreeSet<_>>();
+    let faces = fonts
+        .font_data
+        .iter()
+        .filter_map(|(name, data)| Some((name.as_str(), parse(&data.font, data.index).ok()?)))
+        .collect::<HashMap<_, _>>();
+
+    fonts
+        .families
+        .iter()
+        .map(|(family, chain)| {
+            let chain = chain
+                .iter()
+                .filter_map(|name| faces.get(name.as_str()))
+                .collect::<Vec<_>>();
+            let missing = chars
+                .iter()
+                .copied()
+                .filter(|c| {
+                    !chain
+                        .iter()
+                        .any(|face| face.glyph_index(*c).is_some_and(|glyph| glyph.0 != 0))
+                })
+                .collect();
+            (family.to_owned(), missing)
+        })
+        .collect()
+}
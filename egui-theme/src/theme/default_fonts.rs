@@ -0,0 +1,58 @@
+use egui::{FontData, FontDefinitions};
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+/// The fonts egui bundles, as found in `FontDefinitions::default()`.
+fn builtin_fonts() -> &'static FontDefinitions {
+    static FONTS: OnceLock<FontDefinitions> = OnceLock::new();
+    FONTS.get_or_init(FontDefinitions::default)
+}
+
+/// The names of the fonts egui bundles. These depend on the egui version and its enabled features.
+pub fn builtin_font_names() -> BTreeSet<String> {
+    builtin_fonts().font_data.keys().cloned().collect()
+}
+
+/// Returns true if `name` is one of the fonts bundled with egui.
+///
+/// Built-in fonts are used by the default families and should not be deleted.
+pub fn is_builtin_font(name: &str) -> bool {
+    builtin_fonts().font_data.contains_key(name)
+}
+
+/// The data of the built-in font `name`, including its default tweak.
+pub fn builtin_font(name: &str) -> Option<&'static FontData> {
+    builtin_fonts().font_data.get(name)
+}
+
+/// Returns true if `data` holds the same font as the built-in font `name`, regardless of its tweak.
+/// The bytes of these fonts are not written to themes.
+pub(crate) fn is_builtin_font_data(name: &str, data: &FontData) -> bool {
+    builtin_font(name)
+        .is_some_and(|builtin| builtin.font == data.font && builtin.index == data.index)
+}
+
+/// Restores the built-in fonts that were removed or replaced, and adds them back to the default families they were removed from.
+///
+/// Custom fonts and the order of the fonts that are still in the families are kept, restored fonts are appended to the end of the family.
+/// Built-in fonts that were not replaced keep their tweak.
+pub fn restore_default_fonts(fonts: &mut FontDefinitions) {
+    let builtin = builtin_fonts();
+    for (name, data) in builtin.font_data.iter() {
+        let unchanged = fonts
+            .font_data
+            .get(name)
+            .is_some_and(|existing| is_builtin_font_data(name, existing));
+        if !unchanged {
+            fonts.font_data.insert(name.to_owned(), data.to_owned());
+        }
+    }
+    for (family, names) in builtin.families.iter() {
+        let list = fonts.families.entry(family.to_owned()).or_default();
+        for name in names {
+            if !list.contains(name) {
+                list.push(name.to_owned());
+            }
+        }
+    }
+}
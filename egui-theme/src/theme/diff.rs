@@ -0,0 +1,190 @@
+use egui::{FontDefinitions, Style};
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::{style, EguiTheme, ThemeValue};
+
+/// A single difference between two themes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeChange {
+    /// A style value was added, removed or changed. `key` is the dotted path to the value, such as `visuals.widgets.hovered.bg_fill`.
+    Style {
+        key: String,
+        old: Option<ThemeValue>,
+        new: Option<ThemeValue>,
+    },
+    /// A font was added to `FontDefinitions::font_data`.
+    FontAdded(String),
+    /// A font was removed from `FontDefinitions::font_data`.
+    FontRemoved(String),
+    /// The data or tweak of a font registered under the same name changed.
+    FontChanged(String),
+    /// A font family was added.
+    FamilyAdded { family: String, fonts: Vec<String> },
+    /// A font family was removed.
+    FamilyRemoved { family: String, fonts: Vec<String> },
+    /// Fonts were added to or removed from a family.
+    FamilyChanged {
+        family: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    /// A family contains the same fonts in a different fallback order.
+    FamilyReordered {
+        family: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+}
+
+impl fmt::Display for ThemeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeChange::Style { key, old, new } => match (old, new) {
+                (Some(old), Some(new)) => write!(f, "~ {key}: {old} -> {new}"),
+                (None, Some(new)) => write!(f, "+ {key}: {new}"),
+                (Some(old), None) => write!(f, "- {key}: {old}"),
+                (None, None) => write!(f, "  {key}"),
+            },
+            ThemeChange::FontAdded(name) => write!(f, "+ font `{name}`"),
+            ThemeChange::FontRemoved(name) => write!(f, "- font `{name}`"),
+            ThemeChange::FontChanged(name) => write!(f, "~ font `{name}`"),
+            ThemeChange::FamilyAdded { family, fonts } => {
+                write!(f, "+ family `{family}`: {fonts:?}")
+            }
+            ThemeChange::FamilyRemoved { family, fonts } => {
+                write!(f, "- family `{family}`: {fonts:?}")
+            }
+            ThemeChange::FamilyChanged { family, old, new } => {
+                write!(f, "~ family `{family}`: {old:?} -> {new:?}")
+            }
+            ThemeChange::FamilyReordered { family, old, new } => {
+                write!(f, "~ family `{family}` reordered: {old:?} -> {new:?}")
+            }
+        }
+    }
+}
+
+/// The list of changes between two themes. The `Display` implementation prints one change per line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ThemeDiff {
+    pub changes: Vec<ThemeChange>,
+}
+
+impl ThemeDiff {
+    /// Returns true if both themes produce the same style and fonts.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ThemeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in self.changes.iter() {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two themes by extracting them. Built-in bases are merged before comparing.
+pub fn diff_themes(old: &EguiTheme, new: &EguiTheme) -> ThemeDiff {
+    let (old_style, old_fonts) = old.clone().extract();
+    let (new_style, new_fonts) = new.clone().extract();
+    diff((&old_style, &old_fonts), (&new_style, &new_fonts))
+}
+
+/// Compares two style/font pairs.
+pub fn diff(old: (&Style, &FontDefinitions), new: (&Style, &FontDefinitions)) -> ThemeDiff {
+    let mut changes = Vec::new();
+    diff_style(old.0, new.0, &mut changes);
+    diff_fonts(old.1, new.1, &mut changes);
+    ThemeDiff { changes }
+}
+
+fn diff_style(old: &Style, new: &Style, changes: &mut Vec<ThemeChange>) {
+    let old = style::from_style(old.clone());
+    let new = style::from_style(new.clone());
+    let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+    for key in keys {
+        diff_value(key.to_owned(), old.get(key), new.get(key), changes);
+    }
+}
+
+/// Walks into objects so that only the leaf values that differ are reported.
+fn diff_value(
+    key: String,
+    old: Option<&ThemeValue>,
+    new: Option<&ThemeValue>,
+    changes: &mut Vec<ThemeChange>,
+) {
+    if old == new {
+        return;
+    }
+    if let (Some(ThemeValue::Object(old)), Some(ThemeValue::Object(new))) = (old, new) {
+        let fields = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+        for field in fields {
+            diff_value(
+                format!("{key}.{field}"),
+                old.get(field),
+                new.get(field),
+                changes,
+            );
+        }
+    } else {
+        changes.push(ThemeChange::Style {
+            key,
+            old: old.cloned(),
+            new: new.cloned(),
+        });
+    }
+}
+
+fn diff_fonts(old: &FontDefinitions, new: &FontDefinitions, changes: &mut Vec<ThemeChange>) {
+    for (name, data) in old.font_data.iter() {
+        match new.font_data.get(name) {
+            None => changes.push(ThemeChange::FontRemoved(name.to_owned())),
+            Some(new_data) if new_data != data => {
+                changes.push(ThemeChange::FontChanged(name.to_owned()))
+            }
+            Some(_) => {}
+        }
+    }
+    for name in new.font_data.keys() {
+        if !old.font_data.contains_key(name) {
+            changes.push(ThemeChange::FontAdded(name.to_owned()));
+        }
+    }
+
+    for (family, old_fonts) in old.families.iter() {
+        match new.families.get(family) {
+            None => changes.push(ThemeChange::FamilyRemoved {
+                family: family.to_string(),
+                fonts: old_fonts.to_owned(),
+            }),
+            Some(new_fonts) if new_fonts != old_fonts => {
+                let mut old_sorted = old_fonts.to_owned();
+                let mut new_sorted = new_fonts.to_owned();
+                old_sorted.sort();
+                new_sorted.sort();
+                let family = family.to_string();
+                let old = old_fonts.to_owned();
+                let new = new_fonts.to_owned();
+                changes.push(if old_sorted == new_sorted {
+                    ThemeChange::FamilyReordered { family, old, new }
+                } else {
+                    ThemeChange::FamilyChanged { family, old, new }
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for (family, fonts) in new.families.iter() {
+        if !old.families.contains_key(family) {
+            changes.push(ThemeChange::FamilyAdded {
+                family: family.to_string(),
+                fonts: fonts.to_owned(),
+            });
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+use super::{ThemeFormat, ThemeIssue};
+
+/// Errors that can occur while loading an `EguiTheme`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// The theme was extracted in strict mode and at least one key was unknown, missing or invalid.
+    Strict(Vec<ThemeIssue>),
+    /// The theme was created with an egui version that cannot be migrated to the running version.
+    UnsupportedVersion(String),
+    /// The named base theme inherits from itself, either directly or through one of its parents.
+    BaseCycle(String),
+    /// The requested key is not stored in the theme.
+    MissingKey(String),
+    /// The value of the key could not be converted to or from the requested type.
+    InvalidValue { key: String, error: String },
+    /// The theme could not be serialized to or deserialized from the given format.
+    Format { format: ThemeFormat, error: String },
+    /// The format of the theme could not be detected from its extension or contents.
+    UnknownFormat,
+    /// Support for the format was disabled with feature flags.
+    UnsupportedFormat(ThemeFormat),
+    /// The theme file could not be read or written.
+    Io(String),
+    /// The bytes are not a valid binary theme.
+    InvalidBinary(String),
+    /// The bytes of the named font do not match its content hash.
+    FontHashMismatch(String),
+    /// A font or family cannot be renamed because the new name is already used.
+    NameTaken(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Strict(issues) => {
+                write!(f, "theme was rejected with {} issue(s)", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  {issue}")?;
+                }
+                Ok(())
+            }
+            ThemeError::UnsupportedVersion(version) => write!(
+                f,
+                "themes created with egui {version} cannot be migrated to egui {}",
+                crate::EGUI_VERSION
+            ),
+            ThemeError::BaseCycle(name) => {
+                write!(f, "base theme `{name}` inherits from itself")
+            }
+            ThemeError::MissingKey(key) => write!(f, "key `{key}` does not exist"),
+            ThemeError::InvalidValue { key, error } => {
+                write!(f, "invalid value for `{key}`: {error}")
+            }
+            ThemeError::Format { format, error } => write!(f, "invalid {format} theme: {error}"),
+            ThemeError::UnknownFormat => write!(f, "could not detect the format of the theme"),
+            ThemeError::UnsupportedFormat(format) => {
+                write!(f, "support for {format} themes is disabled")
+            }
+            ThemeError::Io(error) => write!(f, "could not access the theme file: {error}"),
+            ThemeError::InvalidBinary(error) => write!(f, "invalid binary theme: {error}"),
+            ThemeError::FontHashMismatch(name) => {
+                write!(f, "the data of font `{name}` does not match its hash")
+            }
+            ThemeError::NameTaken(name) => write!(f, "`{name}` already exists"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
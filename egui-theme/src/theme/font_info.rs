@@ -0,0 +1,205 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Why a font cannot be used by egui.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontError {
+    /// The data is not a TrueType or OpenType font, or the font face index does not exist.
+    Parse(String),
+    /// The font is missing a table egui needs to lay out or render text.
+    MissingTable(&'static str),
+    /// The font file could not be read.
+    Io(String),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::Parse(error) => write!(f, "not a valid TTF/OTF font: {error}"),
+            FontError::MissingTable(table) => write!(f, "the font has no `{table}` table"),
+            FontError::Io(error) => write!(f, "could not read the font file: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+pub(crate) fn parse(bytes: &[u8], index: u32) -> Result<ttf_parser::Face<'_>, FontError> {
+    ttf_parser::Face::parse(bytes, index).map_err(|error| FontError::Parse(error.to_string()))
+}
+
+/// The first of the names `ids` the face has, tried in order.
+pub(crate) fn face_name(face: &ttf_parser::Face<'_>, ids: &[u16]) -> Option<String> {
+    ids.iter().find_map(|id| {
+        face.names()
+            .into_iter()
+            .filter(|name| name.name_id == *id)
+            .find_map(|name| name.to_string())
+    })
+}
+
+/// Checks that `bytes` contains a TrueType or OpenType font face at `index` that egui can use.
+///
+/// egui panics at text layout time when given data it cannot parse, so fonts should be validated before they are added to `FontDefinitions`.
+pub fn validate_font(bytes: &[u8], index: u32) -> Result<(), FontError> {
+    let face = parse(bytes, index)?;
+    let tables = face.tables();
+    let has_unicode_cmap = tables
+        .cmap
+        .is_some_and(|cmap| cmap.subtables.into_iter().any(|table| table.is_unicode()));
+    if !has_unicode_cmap {
+        return Err(FontError::MissingTable("cmap"));
+    }
+    if tables.glyf.is_none() && tables.cff.is_none() && tables.cff2.is_none() {
+        return Err(FontError::MissingTable("glyf"));
+    }
+    Ok(())
+}
+
+/// A named block of Unicode code points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnicodeRange {
+    pub name: &'static str,
+    pub first: u32,
+    pub last: u32,
+}
+
+impl UnicodeRange {
+    pub const fn new(name: &'static str, first: u32, last: u32) -> Self {
+        Self { name, first, last }
+    }
+
+    /// The number of code points in the range.
+    pub fn count(&self) -> u32 {
+        self.last - self.first + 1
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        (self.first..=self.last).contains(&u32::from(c))
+    }
+
+    /// Every character in the range, skipping code points that are not valid `char`s.
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        (self.first..=self.last).filter_map(char::from_u32)
+    }
+}
+
+/// The Unicode blocks that are checked when inspecting fonts, covering the scripts most commonly used in UIs.
+pub const UNICODE_RANGES: &[UnicodeRange] = &[
+    UnicodeRange::new("Basic Latin", 0x0020, 0x007E),
+    UnicodeRange::new("Latin-1 Supplement", 0x00A0, 0x00FF),
+    UnicodeRange::new("Latin Extended-A", 0x0100, 0x017F),
+    UnicodeRange::new("Latin Extended-B", 0x0180, 0x024F),
+    UnicodeRange::new("IPA Extensions", 0x0250, 0x02AF),
+    UnicodeRange::new("Greek and Coptic", 0x0370, 0x03FF),
+    UnicodeRange::new("Cyrillic", 0x0400, 0x04FF),
+    UnicodeRange::new("Cyrillic Supplement", 0x0500, 0x052F),
+    UnicodeRange::new("Armenian", 0x0530, 0x058F),
+    UnicodeRange::new("Hebrew", 0x0590, 0x05FF),
+    UnicodeRange::new("Arabic", 0x0600, 0x06FF),
+    UnicodeRange::new("Devanagari", 0x0900, 0x097F),
+    UnicodeRange::new("Bengali", 0x0980, 0x09FF),
+    UnicodeRange::new("Thai", 0x0E00, 0x0E7F),
+    UnicodeRange::new("Georgian", 0x10A0, 0x10FF),
+    UnicodeRange::new("Hangul Jamo", 0x1100, 0x11FF),
+    UnicodeRange::new("Latin Extended Additional", 0x1E00, 0x1EFF),
+    UnicodeRange::new("Greek Extended", 0x1F00, 0x1FFF),
+    UnicodeRange::new("General Punctuation", 0x2000, 0x206F),
+    UnicodeRange::new("Currency Symbols", 0x20A0, 0x20CF),
+    UnicodeRange::new("Letterlike Symbols", 0x2100, 0x214F),
+    UnicodeRange::new("Arrows", 0x2190, 0x21FF),
+    UnicodeRange::new("Mathematical Operators", 0x2200, 0x22FF),
+    UnicodeRange::new("Box Drawing", 0x2500, 0x257F),
+    UnicodeRange::new("Geometric Shapes", 0x25A0, 0x25FF),
+    UnicodeRange::new("Miscellaneous Symbols", 0x2600, 0x26FF),
+    UnicodeRange::new("Dingbats", 0x2700, 0x27BF),
+    UnicodeRange::new("CJK Symbols and Punctuation", 0x3000, 0x303F),
+    UnicodeRange::new("Hiragana", 0x3040, 0x309F),
+    UnicodeRange::new("Katakana", 0x30A0, 0x30FF),
+    UnicodeRange::new("CJK Unified Ideographs", 0x4E00, 0x9FFF),
+    UnicodeRange::new("Hangul Syllables", 0xAC00, 0xD7AF),
+    UnicodeRange::new("Private Use Area", 0xE000, 0xF8FF),
+    UnicodeRange::new("Halfwidth and Fullwidth Forms", 0xFF00, 0xFFEF),
+    UnicodeRange::new("Miscellaneous Symbols and Pictographs", 0x1F300, 0x1F5FF),
+    UnicodeRange::new("Emoticons", 0x1F600, 0x1F64F),
+];
+
+/// How many characters of a `UnicodeRange` a font has glyphs for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeCoverage {
+    pub range: UnicodeRange,
+    pub covered: u32,
+}
+
+/// Metadata parsed from the bytes of a font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontInfo {
+    /// The family name stored in the font, such as `Noto Sans CJK JP`.
+    pub family_name: Option<String>,
+    /// The style name stored in the font, such as `Bold Italic`.
+    pub style_name: Option<String>,
+    /// The weight class, from 100 (thin) to 900 (black).
+    pub weight: u16,
+    pub italic: bool,
+    pub monospaced: bool,
+    pub glyph_count: u16,
+    /// The size of the font data in bytes.
+    pub file_size: usize,
+    /// The number of faces in the file. Only font collections have more than one.
+    pub face_count: u32,
+    /// Every range of `UNICODE_RANGES` the font has at least one glyph for.
+    pub coverage: Vec<RangeCoverage>,
+}
+
+impl FontInfo {
+    /// Parses the metadata of the font face at `index`.
+    pub fn parse(bytes: &[u8], index: u32) -> Result<Self, FontError> {
+        let face = parse(bytes, index)?;
+        use ttf_parser::name_id;
+        Ok(Self {
+            family_name: face_name(&face, &[name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY]),
+            style_name: face_name(&face, &[name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY]),
+            weight: face.weight().to_number(),
+            italic: face.is_italic(),
+            monospaced: face.is_monospaced(),
+            glyph_count: face.number_of_glyphs(),
+            file_size: bytes.len(),
+            face_count: ttf_parser::fonts_in_collection(bytes).unwrap_or(1),
+            coverage: coverage(&face),
+        })
+    }
+}
+
+/// Every character the font has a glyph for.
+pub(crate) fn codepoints(face: &ttf_parser::Face<'_>) -> BTreeSet<u32> {
+    let mut codepoints = BTreeSet::new();
+    if let Some(cmap) = face.tables().cmap {
+        for table in cmap
+            .subtables
+            .into_iter()
+            .filter(|table| table.is_unicode())
+        {
+            table.codepoints(|codepoint| {
+                if table
+                    .glyph_index(codepoint)
+                    .is_some_and(|glyph| glyph.0 != 0)
+                {
+                    codepoints.insert(codepoint);
+                }
+            });
+        }
+    }
+    codepoints
+}
+
+fn coverage(face: &ttf_parser::Face<'_>) -> Vec<RangeCoverage> {
+    let codepoints = codepoints(face);
+    UNICODE_RANGES
+        .iter()
+        .map(|range| RangeCoverage {
+            range: *range,
+            covered: codepoints.range(range.first..=range.last).count() as u32,
+        })
+        .filter(|coverage| coverage.covered > 0)
+        .collect()
+}
@@ -0,0 +1,206 @@
+use egui::{FontData, FontTweak};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::fonts::{
+    FontAlias, FONT_ALIASES_KEY, FONT_DATA_KEY, FONT_HASHES_KEY, FONT_REFERENCES_KEY,
+};
+use super::{EguiTheme, ThemeError, ThemeValue};
+
+/// Where the bytes of a font that is referenced instead of embedded in the theme are loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FontSource {
+    /// A file path, usually relative to the theme file.
+    Path(String),
+    /// The hex encoded SHA-256 hash of the font bytes. The resolved bytes are checked against the hash.
+    Hash(String),
+    /// An asset id known to the host application.
+    Asset(String),
+}
+
+impl FontSource {
+    /// Creates a `FontSource::Hash` for the bytes of a font.
+    pub fn hash_of(bytes: &[u8]) -> Self {
+        FontSource::Hash(content_hash(bytes))
+    }
+}
+
+impl fmt::Display for FontSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontSource::Path(path) => write!(f, "path `{path}`"),
+            FontSource::Hash(hash) => write!(f, "hash `{hash}`"),
+            FontSource::Asset(id) => write!(f, "asset `{id}`"),
+        }
+    }
+}
+
+/// A font that is stored in the theme by reference. This mirrors `egui::FontData` with the bytes replaced by their `source`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontReference {
+    pub source: FontSource,
+    #[serde(default)]
+    pub index: u32,
+    #[serde(default)]
+    pub tweak: FontTweak,
+}
+
+/// Supplies the bytes of fonts referenced by a `FontSource`.
+///
+/// This is implemented for any `Fn(&FontSource) -> Result<Vec<u8>, ThemeError>` so a closure that reads from disk or an asset bundle is usually sufficient.
+pub trait FontResolver {
+    fn resolve(&self, source: &FontSource) -> Result<Vec<u8>, ThemeError>;
+}
+
+impl<F> FontResolver for F
+where
+    F: Fn(&FontSource) -> Result<Vec<u8>, ThemeError>,
+{
+    fn resolve(&self, source: &FontSource) -> Result<Vec<u8>, ThemeError> {
+        self(source)
+    }
+}
+
+/// The hex encoded SHA-256 hash of `bytes`.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Fails if `source` is a hash that does not match `bytes`.
+fn verify(name: &str, source: &FontSource, bytes: &[u8]) -> Result<(), ThemeError> {
+    match source {
+        FontSource::Hash(hash) if !hash.eq_ignore_ascii_case(&content_hash(bytes)) => {
+            Err(ThemeError::FontHashMismatch(name.to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl EguiTheme {
+    /// Replaces the embedded bytes of the font `name` with a reference to `source`.
+    /// Other fonts sharing the same data are referenced as well.
+    ///
+    /// The bytes must be supplied by a `FontResolver` when loading the theme with `resolve_fonts`.
+    pub fn reference_font(&mut self, name: &str, source: FontSource) -> Result<(), ThemeError> {
+        let mut aliases = self.font_map::<FontAlias>(FONT_ALIASES_KEY);
+        let font = aliases
+            .get(name)
+            .map_or(name, |alias| alias.font.as_str())
+            .to_owned();
+        let data = self
+            .fonts
+            .get(FONT_DATA_KEY)
+            .and_then(|font_data| font_data.get(&font))
+            .ok_or_else(|| ThemeError::MissingKey(name.to_owned()))?;
+        let data = serde_json::from_value::<FontData>(data.to_owned()).map_err(|error| {
+            ThemeError::InvalidValue {
+                key: font.clone(),
+                error: error.to_string(),
+            }
+        })?;
+        verify(name, &source, &data.font)?;
+
+        let mut references = self.font_references();
+        let shared = aliases
+            .iter()
+            .filter(|(_, alias)| alias.font == font)
+            .map(|(name, _)| name.to_owned())
+            .collect::<Vec<_>>();
+        for name in shared {
+            let alias = aliases.remove(&name).expect("the alias exists");
+            let reference = FontReference {
+                source: source.clone(),
+                index: alias.index,
+                tweak: alias.tweak,
+            };
+            references.insert(name, reference);
+        }
+        let reference = FontReference {
+            source,
+            index: data.index,
+            tweak: data.tweak,
+        };
+        references.insert(font.clone(), reference);
+
+        if let Some(font_data) = self
+            .fonts
+            .get_mut(FONT_DATA_KEY)
+            .and_then(ThemeValue::as_object_mut)
+        {
+            font_data.remove(&font);
+        }
+        let mut hashes = self.font_map::<String>(FONT_HASHES_KEY);
+        hashes.remove(&font);
+        self.set_font_map(FONT_HASHES_KEY, hashes);
+        self.set_font_map(FONT_ALIASES_KEY, aliases);
+        self.set_font_map(FONT_REFERENCES_KEY, references);
+        Ok(())
+    }
+
+    /// Every font that is stored by reference, keyed by the font name.
+    pub fn font_references(&self) -> BTreeMap<String, FontReference> {
+        self.font_map(FONT_REFERENCES_KEY)
+    }
+
+    fn font_map<T: DeserializeOwned>(&self, key: &str) -> BTreeMap<String, T> {
+        self.fonts
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.to_owned()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Stores `map` under `key`, removing the key entirely when the map is empty.
+    fn set_font_map<T: Serialize>(&mut self, key: &str, map: BTreeMap<String, T>) {
+        if map.is_empty() {
+            self.fonts.remove(key);
+        } else {
+            let value = serde_json::to_value(map).expect("serialization error occurred");
+            self.fonts.insert(key.to_owned(), value);
+        }
+    }
+
+    /// Loads the bytes of every referenced font through `resolver` and embeds them in the theme.
+    ///
+    /// Fonts referenced by `FontSource::Hash` are checked against their hash.
+    pub fn resolve_fonts(mut self, resolver: &dyn FontResolver) -> Result<Self, ThemeError> {
+        let references = match self.fonts.remove(FONT_REFERENCES_KEY) {
+            Some(references) => serde_json::from_value::<BTreeMap<String, FontReference>>(
+                references,
+            )
+            .map_err(|error| ThemeError::InvalidValue {
+                key: FONT_REFERENCES_KEY.to_owned(),
+                error: error.to_string(),
+            })?,
+            None => return Ok(self),
+        };
+        let font_data = self
+            .fonts
+            .entry(FONT_DATA_KEY.to_owned())
+            .or_insert_with(|| ThemeValue::Object(Default::default()));
+        if !font_data.is_object() {
+            *font_data = ThemeValue::Object(Default::default());
+        }
+        for (name, reference) in references {
+            let bytes = resolver.resolve(&reference.source)?;
+            verify(&name, &reference.source, &bytes)?;
+            let data = FontData {
+                index: reference.index,
+                tweak: reference.tweak,
+                ..FontData::from_owned(bytes)
+            };
+            font_data
+                .as_object_mut()
+                .expect("font_data is an object")
+                .insert(
+                    name,
+                    serde_json::to_value(data).expect("serialization error occurred"),
+                );
+        }
+        Ok(self)
+    }
+}
@@ -1,16 +1,48 @@
-use egui::{FontData, FontDefinitions};
-use std::collections::{BTreeMap, HashMap};
+use egui::{FontData, FontDefinitions, FontTweak};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-const FONT_DATA_KEY: &str = "font_data";
-const FAMILIES_KEY: &str = "families";
+use super::default_fonts::{
+    builtin_font, builtin_font_names, is_builtin_font, is_builtin_font_data,
+};
+use super::font_source::content_hash;
+use super::report::{Reader, ThemeIssue, ThemeIssueKind, ThemeSection};
+use super::{validate_font, FontReference};
 
-/// Removes the default font data when serializing the fonts.
+pub(crate) const FONT_DATA_KEY: &str = "font_data";
+pub(crate) const FONT_HASHES_KEY: &str = "font_hashes";
+pub(crate) const FONT_ALIASES_KEY: &str = "font_aliases";
+pub(crate) const FONT_REFERENCES_KEY: &str = "font_references";
+pub(crate) const FONT_TWEAKS_KEY: &str = "font_tweaks";
+pub(crate) const REMOVED_FONTS_KEY: &str = "removed_fonts";
+pub(crate) const FAMILIES_KEY: &str = "families";
+
+/// A font whose data is identical to the font named `font` and is only stored once.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct FontAlias {
+    pub(crate) font: String,
+    pub(crate) index: u32,
+    pub(crate) tweak: FontTweak,
+}
+
+/// Removes the unchanged built-in font data when serializing the fonts.
 /// This is done to trim down the size of the data saved into the theme.
-fn remove_default_fonts(mut font_data: BTreeMap<String, FontData>) -> BTreeMap<String, FontData> {
-    for font_name in crate::DEFAULT_FONTS {
-        font_data.remove(&font_name.to_owned());
-    }
-    font_data
+///
+/// Only the tweaks of built-in fonts whose tweak was changed are kept.
+fn remove_default_fonts(
+    mut font_data: BTreeMap<String, FontData>,
+) -> (BTreeMap<String, FontData>, BTreeMap<String, FontTweak>) {
+    let mut tweaks = BTreeMap::new();
+    font_data.retain(|name, data| {
+        if !is_builtin_font_data(name, data) {
+            return true;
+        }
+        if builtin_font(name).is_some_and(|builtin| builtin.tweak != data.tweak) {
+            tweaks.insert(name.to_owned(), data.tweak);
+        }
+        false
+    });
+    (font_data, tweaks)
 }
 /// Helper function for serializing the `egui::FontDefinitions`
 pub fn from_fonts(
@@ -19,13 +51,66 @@ pub fn from_fonts(
         families,
     }: FontDefinitions,
 ) -> HashMap<String, serde_json::Value> {
-    let font_data = remove_default_fonts(font_data);
+    // The built-in fonts are always there when extracting, unless they are listed as removed.
+    let removed = builtin_font_names()
+        .into_iter()
+        .filter(|name| !font_data.contains_key(name))
+        .collect::<BTreeSet<_>>();
+    let (font_data, tweaks) = remove_default_fonts(font_data);
     let mut hash_map = HashMap::new();
 
+    // Fonts with identical data are only stored once, every other name becomes an alias of the first one.
+    let mut unique = BTreeMap::new();
+    let mut hashes = BTreeMap::new();
+    let mut aliases = BTreeMap::new();
+    let mut stored = HashMap::<String, String>::new();
+    for (name, data) in font_data {
+        let hash = content_hash(&data.font);
+        match stored.get(&hash) {
+            Some(font) => {
+                let alias = FontAlias {
+                    font: font.clone(),
+                    index: data.index,
+                    tweak: data.tweak,
+                };
+                aliases.insert(name, alias);
+            }
+            None => {
+                stored.insert(hash.clone(), name.clone());
+                hashes.insert(name.clone(), hash);
+                unique.insert(name, data);
+            }
+        }
+    }
+
     hash_map.insert(
         FONT_DATA_KEY.to_owned(),
-        serde_json::to_value(font_data).expect("serialization error occurred"),
+        serde_json::to_value(unique).expect("serialization error occurred"),
     );
+    if !hashes.is_empty() {
+        hash_map.insert(
+            FONT_HASHES_KEY.to_owned(),
+            serde_json::to_value(hashes).expect("serialization error occurred"),
+        );
+    }
+    if !aliases.is_empty() {
+        hash_map.insert(
+            FONT_ALIASES_KEY.to_owned(),
+            serde_json::to_value(aliases).expect("serialization error occurred"),
+        );
+    }
+    if !tweaks.is_empty() {
+        hash_map.insert(
+            FONT_TWEAKS_KEY.to_owned(),
+            serde_json::to_value(tweaks).expect("serialization error occurred"),
+        );
+    }
+    if !removed.is_empty() {
+        hash_map.insert(
+            REMOVED_FONTS_KEY.to_owned(),
+            serde_json::to_value(removed).expect("serialization error occurred"),
+        );
+    }
 
     // Workaround due to FontFamily not properly serializing to "String" when attempting to serialize the BTreeMap<FontFamily, Vec<String>>
     let families = {
@@ -48,30 +133,115 @@ pub fn from_fonts(
 }
 
 /// Helper function for deserializing the `egui::FontDefinitions`
-pub fn to_fonts(hash_map: HashMap<String, serde_json::Value>) -> FontDefinitions {
+/// Any keys that are missing, unknown or fail to deserialize are reported alongside the resulting fonts.
+///
+/// Fonts whose data does not match their recorded hash are reported as corrupt, fonts egui cannot parse are reported as invalid and fonts that are still stored as a `FontReference` are reported as unresolved.
+/// All of them are left out of the definitions and families, as egui panics at text layout time on invalid or missing font data.
+pub fn to_fonts(
+    hash_map: &HashMap<String, serde_json::Value>,
+) -> (FontDefinitions, Vec<ThemeIssue>) {
     let mut fonts = FontDefinitions::default();
+    let mut reader = Reader::new(ThemeSection::Fonts, hash_map);
+    let mut missing = BTreeSet::new();
 
-    if let Some(value) = hash_map.get(&FONT_DATA_KEY.to_owned()) {
-        if let Ok(font_data) =
-            serde_json::from_value::<BTreeMap<String, FontData>>(value.to_owned())
-        {
-            for (k, v) in font_data.iter() {
-                let _ = fonts.font_data.insert(k.to_owned(), v.to_owned());
+    let removed = reader
+        .read_optional::<BTreeSet<String>>(REMOVED_FONTS_KEY)
+        .unwrap_or_default();
+    for name in removed {
+        if fonts.font_data.remove(&name).is_none() {
+            reader.invalid(
+                REMOVED_FONTS_KEY,
+                format!("`{name}` is not a built-in font"),
+            );
+        }
+    }
+
+    let font_data = reader
+        .read::<BTreeMap<String, FontData>>(FONT_DATA_KEY)
+        .unwrap_or_default();
+    let hashes = reader
+        .read_optional::<BTreeMap<String, String>>(FONT_HASHES_KEY)
+        .unwrap_or_default();
+    for (name, data) in font_data {
+        match hashes.get(&name) {
+            Some(hash) if !hash.eq_ignore_ascii_case(&content_hash(&data.font)) => {
+                reader.issue(&name, ThemeIssueKind::CorruptFont);
+                missing.insert(name);
             }
+            _ => match validate_font(&data.font, data.index) {
+                Ok(()) => {
+                    let _ = fonts.font_data.insert(name, data);
+                }
+                Err(error) => {
+                    reader.issue(&name, ThemeIssueKind::InvalidFont(error.to_string()));
+                    missing.insert(name);
+                }
+            },
         }
     }
 
-    if let Some(value) = hash_map.get(&FAMILIES_KEY.to_owned()) {
-        // Workaround due to FontFamily not properly serializing to "String" when attempting to serialize the BTreeMap<FontFamily, Vec<String>>
-        if let Ok(families) = serde_json::from_value::<Vec<(String, Vec<String>)>>(value.to_owned())
-        {
-            // println!("{:?}", families);
-            for (family, list) in families {
-                let family = serde_json::from_str(family.as_str()).expect("serialization failed");
-                fonts.families.insert(family, list.clone());
+    let aliases = reader
+        .read_optional::<BTreeMap<String, FontAlias>>(FONT_ALIASES_KEY)
+        .unwrap_or_default();
+    for (name, alias) in aliases {
+        match fonts.font_data.get(&alias.font) {
+            Some(data) => {
+                let data = FontData {
+                    font: data.font.clone(),
+                    index: alias.index,
+                    tweak: alias.tweak,
+                };
+                fonts.font_data.insert(name, data);
             }
-        };
+            None => {
+                // A corrupt font has already been reported, only report aliases of fonts that were never stored.
+                if !missing.contains(&alias.font) {
+                    reader.invalid(
+                        FONT_ALIASES_KEY,
+                        format!("`{name}` is an alias of the unknown font `{}`", alias.font),
+                    );
+                }
+                missing.insert(name);
+            }
+        }
+    }
+
+    let tweaks = reader
+        .read_optional::<BTreeMap<String, FontTweak>>(FONT_TWEAKS_KEY)
+        .unwrap_or_default();
+    for (name, tweak) in tweaks {
+        match fonts.font_data.get_mut(&name) {
+            Some(data) => data.tweak = tweak,
+            None if is_builtin_font(&name) => reader.invalid(
+                FONT_TWEAKS_KEY,
+                format!("the built-in font `{name}` was removed from the theme"),
+            ),
+            None => reader.invalid(FONT_TWEAKS_KEY, format!("`{name}` is an unknown font")),
+        }
+    }
+
+    let unresolved = reader
+        .read_optional::<BTreeMap<String, FontReference>>(FONT_REFERENCES_KEY)
+        .unwrap_or_default();
+    for name in unresolved.into_keys() {
+        reader.issue(&name, ThemeIssueKind::UnresolvedFont);
+        missing.insert(name);
+    }
+
+    // Workaround due to FontFamily not properly serializing to "String" when attempting to serialize the BTreeMap<FontFamily, Vec<String>>
+    if let Some(families) = reader.read::<Vec<(String, Vec<String>)>>(FAMILIES_KEY) {
+        for (family, list) in families {
+            match serde_json::from_str(family.as_str()) {
+                Ok(family) => {
+                    fonts.families.insert(family, list);
+                }
+                Err(error) => reader.invalid(FAMILIES_KEY, format!("`{family}`: {error}")),
+            }
+        }
+    }
+    for list in fonts.families.values_mut() {
+        list.retain(|name| !missing.contains(name));
     }
 
-    fonts
+    (fonts, reader.finish())
 }
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::path::Path;
+
+use super::{EguiTheme, ThemeError};
+
+/// The on-disk formats an `EguiTheme` can be saved as and loaded from.
+///
+/// JSON is always available. RON, TOML and YAML are enabled by the `ron`, `toml` and `yaml` features respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeFormat {
+    Ron,
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl fmt::Display for ThemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeFormat::Ron => write!(f, "RON"),
+            ThemeFormat::Json => write!(f, "JSON"),
+            ThemeFormat::Toml => write!(f, "TOML"),
+            ThemeFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+impl ThemeFormat {
+    /// Returns the format for a file extension. `.eguitheme` files are RON.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "ron" | "eguitheme" => Some(ThemeFormat::Ron),
+            "json" => Some(ThemeFormat::Json),
+            "toml" => Some(ThemeFormat::Toml),
+            "yaml" | "yml" => Some(ThemeFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Returns the format for the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Guesses the format from the first meaningful line of `contents`.
+    pub fn detect(contents: &str) -> Option<Self> {
+        let line = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("//"))?;
+        if line.starts_with('{') {
+            Some(ThemeFormat::Json)
+        } else if line.starts_with('(') || line.starts_with("EguiTheme") {
+            Some(ThemeFormat::Ron)
+        } else if is_yaml_line(line) {
+            Some(ThemeFormat::Yaml)
+        } else if is_toml_line(line) {
+            Some(ThemeFormat::Toml)
+        } else {
+            None
+        }
+    }
+
+    /// All of the extensions that can be loaded, for use in file dialog filters.
+    pub fn extensions() -> &'static [&'static str] {
+        &["eguitheme", "ron", "json", "toml", "yaml", "yml"]
+    }
+}
+
+/// Whether `key` is a plain or quoted key. Values may contain `:` and `=`, keys never do.
+fn is_key(key: &str) -> bool {
+    let key = key.trim();
+    let quoted = key.len() >= 2
+        && (key.starts_with('"') && key.ends_with('"')
+            || key.starts_with('\'') && key.ends_with('\''));
+    quoted
+        || !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// A document start, a list item or a `key: value` line.
+fn is_yaml_line(line: &str) -> bool {
+    if line.starts_with("---") || line.starts_with("- ") {
+        return true;
+    }
+    line.split_once(':').is_some_and(|(key, value)| {
+        is_key(key) && (value.is_empty() || value.starts_with(char::is_whitespace))
+    })
+}
+
+/// A `[table]` header or a `key = value` line.
+fn is_toml_line(line: &str) -> bool {
+    if line.starts_with('[') && line.ends_with(']') {
+        return true;
+    }
+    line.split_once('=')
+        .is_some_and(|(key, value)| is_key(key) && !value.trim().is_empty())
+}
+
+impl EguiTheme {
+    /// Serializes the theme into `format`. JSON and RON are written in their pretty printed forms so themes are easy to review.
+    pub fn to_string_as(&self, format: ThemeFormat) -> Result<String, ThemeError> {
+        let error = |error: &dyn fmt::Display| ThemeError::Format {
+            format,
+            error: error.to_string(),
+        };
+        match format {
+            ThemeFormat::Json => serde_json::to_string_pretty(self).map_err(|e| error(&e)),
+            #[cfg(feature = "ron")]
+            ThemeFormat::Ron => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|e| error(&e)),
+            #[cfg(feature = "toml")]
+            ThemeFormat::Toml => {
+                toml::to_string(&toml_nulls::encode(self.clone())).map_err(|e| error(&e))
+            }
+            #[cfg(feature = "yaml")]
+            ThemeFormat::Yaml => serde_yaml::to_string(self).map_err(|e| error(&e)),
+            #[allow(unreachable_patterns)]
+            _ => Err(ThemeError::UnsupportedFormat(format)),
+        }
+    }
+
+    /// Deserializes a theme from `contents` in the given `format`.
+    pub fn from_str_as(contents: &str, format: ThemeFormat) -> Result<Self, ThemeError> {
+        let error = |error: &dyn fmt::Display| ThemeError::Format {
+            format,
+            error: error.to_string(),
+        };
+        match format {
+            ThemeFormat::Json => serde_json::from_str(contents).map_err(|e| error(&e)),
+            #[cfg(feature = "ron")]
+            ThemeFormat::Ron => ron::from_str(contents).map_err(|e| error(&e)),
+            #[cfg(feature = "toml")]
+            ThemeFormat::Toml => toml::from_str(contents)
+                .map(toml_nulls::decode)
+                .map_err(|e| error(&e)),
+            #[cfg(feature = "yaml")]
+            ThemeFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| error(&e)),
+            #[allow(unreachable_patterns)]
+            _ => Err(ThemeError::UnsupportedFormat(format)),
+        }
+    }
+
+    /// Deserializes a theme from `contents`, detecting the format from the contents.
+    pub fn from_str_detect(contents: &str) -> Result<Self, ThemeError> {
+        let format = ThemeFormat::detect(contents).ok_or(ThemeError::UnknownFormat)?;
+        Self::from_str_as(contents, format)
+    }
+
+    /// Saves the theme to `path`. The format is taken from the extension, paths without an extension are saved as RON.
+    ///
+    /// Returns `ThemeError::UnknownFormat` for any other extension, binary themes are written with `to_bytes` instead.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ThemeError> {
+        let path = path.as_ref();
+        let format = match path.extension() {
+            Some(_) => ThemeFormat::from_path(path).ok_or(ThemeError::UnknownFormat)?,
+            None => ThemeFormat::Ron,
+        };
+        let contents = self.to_string_as(format)?;
+        std::fs::write(path, contents).map_err(|error| ThemeError::Io(error.to_string()))
+    }
+
+    /// Loads a theme from `path`. The format is taken from the extension, falling back to detecting it from the contents.
+    /// Binary themes created with `to_bytes` are recognized by their header.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|error| ThemeError::Io(error.to_string()))?;
+        #[cfg(feature = "binary")]
+        if Self::is_binary(&bytes) {
+            return Self::from_bytes(&bytes);
+        }
+        let contents = String::from_utf8(bytes).map_err(|_| ThemeError::UnknownFormat)?;
+        match ThemeFormat::from_path(path) {
+            Some(format) => Self::from_str_as(&contents, format),
+            None => Self::from_str_detect(&contents),
+        }
+    }
+}
+
+/// TOML has no way to represent `null`, which the theme uses for unset `Option`s such as `override_text_style`.
+/// Style values are never empty objects, so nulls in the `style` section are written as empty tables and converted back when loading.
+#[cfg(feature = "toml")]
+mod toml_nulls {
+    use super::super::{EguiTheme, ThemeValue};
+
+    fn map(value: &mut ThemeValue, f: &dyn Fn(&mut ThemeValue)) {
+        f(value);
+        match value {
+            ThemeValue::Array(values) => values.iter_mut().for_each(|value| map(value, f)),
+            ThemeValue::Object(values) => values.values_mut().for_each(|value| map(value, f)),
+            _ => {}
+        }
+    }
+
+    pub(super) fn encode(mut theme: EguiTheme) -> EguiTheme {
+        for value in theme.style.values_mut() {
+            map(value, &|value| {
+                if value.is_null() {
+                    *value = ThemeValue::Object(Default::default());
+                }
+            });
+        }
+        theme
+    }
+
+    pub(super) fn decode(mut theme: EguiTheme) -> EguiTheme {
+        for value in theme.style.values_mut() {
+            map(value, &|value| {
+                if value.as_object().is_some_and(|object| object.is_empty()) {
+                    *value = ThemeValue::Null;
+                }
+            });
+        }
+        theme
+    }
+}
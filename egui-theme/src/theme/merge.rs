@@ -0,0 +1,196 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::base::FONT_SET_KEYS;
+use super::fonts::FONT_DATA_KEY;
+use super::{base, EguiTheme, ThemeSection, ThemeValue};
+
+/// A value that was changed differently on both sides of a three-way merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeConflict {
+    pub section: ThemeSection,
+    /// The dotted path to the conflicting value, such as `visuals.widgets.hovered.bg_fill`.
+    /// Conflicting fonts are reported as `font_data`, with all the keys that store the fonts in the values.
+    pub key: String,
+    pub base: Option<ThemeValue>,
+    pub ours: Option<ThemeValue>,
+    pub theirs: Option<ThemeValue>,
+}
+
+/// The result of `merge_themes`.
+pub struct ThemeMerge {
+    /// The merged theme. Conflicting values are taken from `ours`.
+    pub theme: EguiTheme,
+    pub conflicts: Vec<ThemeConflict>,
+}
+
+/// Three-way merges two themes that were both edited from a common `base`.
+///
+/// Values are merged field by field, so edits to different fields of the same key (e.g. two different widget colors in `visuals.widgets`) merge cleanly.
+/// The fonts and their families refer to each other, so they are merged as a whole and conflict if both sides changed them.
+/// Built-in bases are merged into each theme before comparing.
+pub fn merge_themes(base: &EguiTheme, ours: &EguiTheme, theirs: &EguiTheme) -> ThemeMerge {
+    let base = base::merge_builtin(base.clone());
+    let ours = base::merge_builtin(ours.clone());
+    let theirs = base::merge_builtin(theirs.clone());
+    let mut conflicts = Vec::new();
+
+    let style = merge_maps(
+        ThemeSection::Style,
+        &base.style,
+        &ours.style,
+        &theirs.style,
+        &mut conflicts,
+    );
+    let (base_fonts, base_rest) = split_font_set(&base.fonts);
+    let (our_fonts, our_rest) = split_font_set(&ours.fonts);
+    let (their_fonts, their_rest) = split_font_set(&theirs.fonts);
+    let mut fonts = merge_maps(
+        ThemeSection::Fonts,
+        &base_rest,
+        &our_rest,
+        &their_rest,
+        &mut conflicts,
+    );
+    let font_set = if our_fonts == their_fonts || their_fonts == base_fonts {
+        our_fonts
+    } else if our_fonts == base_fonts {
+        their_fonts
+    } else {
+        let to_value = |fonts: &serde_json::Map<String, ThemeValue>| {
+            Some(ThemeValue::Object(fonts.to_owned()))
+        };
+        conflicts.push(ThemeConflict {
+            section: ThemeSection::Fonts,
+            key: FONT_DATA_KEY.to_owned(),
+            base: to_value(&base_fonts),
+            ours: to_value(&our_fonts),
+            theirs: to_value(&their_fonts),
+        });
+        our_fonts
+    };
+    fonts.extend(font_set);
+    // Only named bases remain at this point, a conflict keeps our parent.
+    let theme_base = if ours.base == theirs.base || theirs.base == base.base {
+        ours.base.clone()
+    } else if ours.base == base.base {
+        theirs.base.clone()
+    } else {
+        let to_value = |theme: &EguiTheme| {
+            theme
+                .base
+                .as_ref()
+                .and_then(|base| serde_json::to_value(base).ok())
+        };
+        conflicts.push(ThemeConflict {
+            section: ThemeSection::Style,
+            key: "base".to_owned(),
+            base: to_value(&base),
+            ours: to_value(&ours),
+            theirs: to_value(&theirs),
+        });
+        ours.base.clone()
+    };
+
+    ThemeMerge {
+        theme: EguiTheme {
+            style,
+            fonts,
+            base: theme_base,
+            ..ours
+        },
+        conflicts,
+    }
+}
+
+/// Splits the fonts section into the `FONT_SET_KEYS`, which are only consistent with each other, and the other keys.
+fn split_font_set(
+    fonts: &HashMap<String, ThemeValue>,
+) -> (
+    serde_json::Map<String, ThemeValue>,
+    HashMap<String, ThemeValue>,
+) {
+    let (font_set, rest): (HashMap<_, _>, HashMap<_, _>) = fonts
+        .iter()
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .partition(|(key, _)| FONT_SET_KEYS.contains(&key.as_str()));
+    (font_set.into_iter().collect(), rest)
+}
+
+fn merge_maps(
+    section: ThemeSection,
+    base: &HashMap<String, ThemeValue>,
+    ours: &HashMap<String, ThemeValue>,
+    theirs: &HashMap<String, ThemeValue>,
+    conflicts: &mut Vec<ThemeConflict>,
+) -> HashMap<String, ThemeValue> {
+    let keys = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect::<BTreeSet<_>>();
+    let mut merged = HashMap::new();
+    for key in keys {
+        let value = merge_value(
+            section,
+            key,
+            base.get(key),
+            ours.get(key),
+            theirs.get(key),
+            conflicts,
+        );
+        if let Some(value) = value {
+            merged.insert(key.to_owned(), value);
+        }
+    }
+    merged
+}
+
+fn merge_value(
+    section: ThemeSection,
+    key: &str,
+    base: Option<&ThemeValue>,
+    ours: Option<&ThemeValue>,
+    theirs: Option<&ThemeValue>,
+    conflicts: &mut Vec<ThemeConflict>,
+) -> Option<ThemeValue> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+    if let (Some(ThemeValue::Object(ours)), Some(ThemeValue::Object(theirs))) = (ours, theirs) {
+        let base = match base {
+            Some(ThemeValue::Object(base)) => Some(base),
+            _ => None,
+        };
+        let fields = ours
+            .keys()
+            .chain(theirs.keys())
+            .chain(base.into_iter().flat_map(|base| base.keys()))
+            .collect::<BTreeSet<_>>();
+        let mut merged = serde_json::Map::new();
+        for field in fields {
+            let value = merge_value(
+                section,
+                &format!("{key}.{field}"),
+                base.and_then(|base| base.get(field)),
+                ours.get(field),
+                theirs.get(field),
+                conflicts,
+            );
+            if let Some(value) = value {
+                merged.insert(field.to_owned(), value);
+            }
+        }
+        return Some(ThemeValue::Object(merged));
+    }
+    conflicts.push(ThemeConflict {
+        section,
+        key: key.to_owned(),
+        base: base.cloned(),
+        ours: ours.cloned(),
+        theirs: theirs.cloned(),
+    });
+    ours.cloned()
+}
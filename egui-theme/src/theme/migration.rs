@@ -0,0 +1,174 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use super::{ThemeError, ThemeValue};
+
+/// How a loaded theme relates to the versions of egui and egui-theme that are currently running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    /// The theme was created with exactly the same egui and egui-theme versions.
+    Exact,
+    /// The theme was created with the same egui version but a different egui-theme version.
+    Compatible,
+    /// The theme was created with an older egui version and a migration path to the current version exists.
+    NeedsMigration,
+    /// The theme was created with a newer egui version, the version could not be parsed or no migration path exists.
+    Unsupported,
+}
+
+/// The function signature used by migrations. It receives the `style` and `fonts` key maps respectively.
+pub type MigrationFn =
+    Box<dyn Fn(&mut HashMap<String, ThemeValue>, &mut HashMap<String, ThemeValue>)>;
+
+/// A single step that rewrites the intermediate key maps from one egui version layout to the next.
+pub struct Migration {
+    from: Version,
+    to: Version,
+    apply: MigrationFn,
+}
+
+impl Migration {
+    /// Creates a migration from the egui version `from` to the egui version `to`.
+    ///
+    /// Only the major and minor version numbers are considered as egui does not change its layout between patch releases.
+    /// Returns `ThemeError::InvalidValue` if either version cannot be parsed.
+    pub fn new(
+        from: &str,
+        to: &str,
+        apply: impl Fn(&mut HashMap<String, ThemeValue>, &mut HashMap<String, ThemeValue>) + 'static,
+    ) -> Result<Self, ThemeError> {
+        Ok(Self {
+            from: Version::parse_key(from)?,
+            to: Version::parse_key(to)?,
+            apply: Box::new(apply),
+        })
+    }
+
+    /// Creates a migration that only renames `style` keys, such as `visuals.*` fields that egui renamed between versions.
+    pub fn rename_style_keys(
+        from: &str,
+        to: &str,
+        renames: &'static [(&'static str, &'static str)],
+    ) -> Result<Self, ThemeError> {
+        Self::new(from, to, move |style, _| {
+            for (old_key, new_key) in renames {
+                if let Some(value) = style.remove(*old_key) {
+                    style.insert((*new_key).to_owned(), value);
+                }
+            }
+        })
+    }
+}
+
+/// The collection of migrations that can be used to upgrade themes from previous egui versions.
+///
+/// egui 0.18 is the first version supported by this format so egui-theme does not ship any migrations yet. Applications can register their own steps with `register`.
+#[derive(Default)]
+pub struct MigrationRegistry {
+    migrations: Vec<Migration>,
+}
+
+impl MigrationRegistry {
+    /// Registers an additional migration step.
+    pub fn register(&mut self, migration: Migration) {
+        self.migrations.push(migration);
+    }
+
+    /// Finds the shortest chain of migrations that upgrades `from` to `to`.
+    fn path(&self, from: Version, to: Version) -> Option<Vec<&Migration>> {
+        // Breadth first search over the versions, remembering the step that first reached each of them.
+        let mut reached: HashMap<Version, Option<&Migration>> = HashMap::new();
+        reached.insert(from, None);
+        let mut pending = VecDeque::from([from]);
+        while let Some(current) = pending.pop_front() {
+            if current == to {
+                break;
+            }
+            // Each step must move forward without overshooting, so the search always ends.
+            let steps = self.migrations.iter().filter(|migration| {
+                migration.from == current && migration.to > current && migration.to <= to
+            });
+            for step in steps {
+                if let Entry::Vacant(entry) = reached.entry(step.to) {
+                    entry.insert(Some(step));
+                    pending.push_back(step.to);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = to;
+        while let Some(step) = *reached.get(&current)? {
+            path.push(step);
+            current = step.from;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Classifies a theme created with the given versions against the running crate.
+    pub(crate) fn compatibility(
+        &self,
+        egui_theme_version: &str,
+        egui_version: &str,
+    ) -> Compatibility {
+        let (current, theme) = match (
+            Version::parse(crate::EGUI_VERSION),
+            Version::parse(egui_version),
+        ) {
+            (Some(current), Some(theme)) => (current, theme),
+            _ => return Compatibility::Unsupported,
+        };
+        if theme == current {
+            if egui_theme_version == crate::EGUI_THEME_VERSION {
+                Compatibility::Exact
+            } else {
+                Compatibility::Compatible
+            }
+        } else if theme < current && self.path(theme, current).is_some() {
+            Compatibility::NeedsMigration
+        } else {
+            Compatibility::Unsupported
+        }
+    }
+
+    /// Applies every migration required to bring the key maps from `egui_version` up to the running egui version.
+    pub(crate) fn migrate(
+        &self,
+        egui_version: &str,
+        style: &mut HashMap<String, ThemeValue>,
+        fonts: &mut HashMap<String, ThemeValue>,
+    ) -> Result<(), ThemeError> {
+        let unsupported = || ThemeError::UnsupportedVersion(egui_version.to_owned());
+        let current = Version::parse(crate::EGUI_VERSION).ok_or_else(unsupported)?;
+        let theme = Version::parse(egui_version).ok_or_else(unsupported)?;
+        let path = self.path(theme, current).ok_or_else(unsupported)?;
+        for migration in path {
+            (migration.apply)(style, fonts);
+        }
+        Ok(())
+    }
+}
+
+/// The major/minor version of egui. Patch versions are ignored as they never change the style layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl Version {
+    fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        Some(Self { major, minor })
+    }
+
+    fn parse_key(version: &str) -> Result<Self, ThemeError> {
+        Self::parse(version).ok_or_else(|| ThemeError::InvalidValue {
+            key: version.to_owned(),
+            error: "not a valid egui version".to_owned(),
+        })
+    }
+}
@@ -2,8 +2,51 @@ use egui::{FontDefinitions, Style};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+mod access;
+mod base;
+#[cfg(feature = "binary")]
+pub(crate) mod binary;
+mod coverage;
+mod default_fonts;
+mod diff;
+mod error;
+mod font_info;
+mod font_source;
 mod fonts;
+mod format;
+mod merge;
+mod migration;
+mod references;
+mod rename;
+mod report;
 mod style;
+#[cfg(feature = "subset")]
+mod subset;
+pub(crate) mod system_fonts;
+
+pub use base::{ThemeBase, ThemeResolver};
+#[cfg(feature = "binary")]
+pub use binary::ThemeCompression;
+pub use coverage::missing_glyphs;
+pub use default_fonts::{builtin_font, builtin_font_names, is_builtin_font, restore_default_fonts};
+pub use diff::{diff, diff_themes, ThemeChange, ThemeDiff};
+pub use error::ThemeError;
+pub use font_info::{
+    validate_font, FontError, FontInfo, RangeCoverage, UnicodeRange, UNICODE_RANGES,
+};
+pub use font_source::{FontReference, FontResolver, FontSource};
+pub use format::ThemeFormat;
+pub use merge::{merge_themes, ThemeConflict, ThemeMerge};
+pub use migration::{Compatibility, Migration, MigrationFn, MigrationRegistry};
+pub use references::{
+    dangling_references, families_using_font, fix_dangling_references, move_font, remove_family,
+    remove_font, text_styles_using_family, DanglingReference,
+};
+pub use rename::{rename_family, rename_font};
+pub use report::{ThemeIssue, ThemeIssueKind, ThemeSection};
+#[cfg(feature = "subset")]
+pub use subset::{latin1_chars, subset_font, subset_fonts};
+pub use system_fonts::{scan_font_dirs, system_font_dirs, system_fonts, SystemFont};
 
 type ThemeValue = serde_json::Value;
 
@@ -13,7 +56,7 @@ type ThemeValue = serde_json::Value;
 ///
 /// # Important
 /// This should only be used during the Serialization/Deserialization process. Once the loading has completed, this should be extracted directly to an egui::Context as soon as it has been fully loaded.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct EguiTheme {
     /// Version of egui_theme that the theme was created with
     pub(crate) egui_theme_version: String,
@@ -23,6 +66,9 @@ pub struct EguiTheme {
     pub(crate) style: HashMap<String, ThemeValue>,
     /// Used for
     pub(crate) fonts: HashMap<String, ThemeValue>,
+    /// The parent theme that any keys missing from `style` and `fonts` are inherited from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) base: Option<ThemeBase>,
 }
 
 impl EguiTheme {
@@ -37,17 +83,140 @@ impl EguiTheme {
             egui_version: crate::EGUI_VERSION.to_owned(),
             style,
             fonts,
+            base: None,
+        }
+    }
+
+    /// Create a new theme that only stores the keys that differ from `base`.
+    /// `resolver` is used to load the parent when `base` is a `ThemeBase::Theme`.
+    pub fn new_with_base(
+        style: Style,
+        font_definitions: FontDefinitions,
+        base: ThemeBase,
+        resolver: &dyn ThemeResolver,
+    ) -> Result<Self, ThemeError> {
+        let parent = base::resolve_parent(base.clone(), resolver)?;
+        let theme = Self::new(style, font_definitions);
+        Ok(Self {
+            style: base::strip(theme.style, &parent.style),
+            fonts: base::strip_fonts(theme.fonts, &parent.fonts),
+            base: Some(base),
+            ..theme
+        })
+    }
+
+    /// Create a new theme that only stores the values that differ from `baseline` and the default fonts.
+    ///
+    /// The baseline is not recorded in the theme, so the same baseline must be supplied to `with_baseline` when loading.
+    /// Use `new_with_base` with `ThemeBase::Dark` or `ThemeBase::Light` to record egui's built-in styles instead.
+    pub fn new_sparse(style: Style, font_definitions: FontDefinitions, baseline: &Style) -> Self {
+        let parent = Self::new(baseline.clone(), FontDefinitions::default());
+        let theme = Self::new(style, font_definitions);
+        Self {
+            style: base::strip(theme.style, &parent.style),
+            fonts: base::strip_fonts(theme.fonts, &parent.fonts),
+            ..theme
+        }
+    }
+
+    /// Fills in every value missing from a theme created with `new_sparse` from `baseline` and the default fonts.
+    pub fn with_baseline(self, baseline: &Style) -> Self {
+        let parent = Self::new(baseline.clone(), FontDefinitions::default());
+        Self {
+            style: base::merge(parent.style, self.style),
+            fonts: base::merge_fonts(parent.fonts, self.fonts),
+            ..self
         }
     }
 
+    /// The parent theme this theme inherits from, if any.
+    pub fn base(&self) -> Option<&ThemeBase> {
+        self.base.as_ref()
+    }
+
+    /// Merges the theme with all of its parents, producing a standalone theme without a base.
+    /// `resolver` is used to load every `ThemeBase::Theme` in the chain.
+    pub fn resolve_base(self, resolver: &dyn ThemeResolver) -> Result<Self, ThemeError> {
+        base::resolve(self, resolver, &mut Vec::new())
+    }
+
+    /// Version of egui_theme that the theme was created with
+    pub fn egui_theme_version(&self) -> &str {
+        &self.egui_theme_version
+    }
+
+    /// Version of egui that the theme was created with
+    pub fn egui_version(&self) -> &str {
+        &self.egui_version
+    }
+
+    /// Compares the versions the theme was created with against the running crate.
+    pub fn compatibility(&self, registry: &MigrationRegistry) -> Compatibility {
+        registry.compatibility(&self.egui_theme_version, &self.egui_version)
+    }
+
+    /// Upgrades the theme to the running egui version using the migrations in `registry`.
+    ///
+    /// Themes that are already on the running egui version are left untouched.
+    pub fn migrate(&mut self, registry: &MigrationRegistry) -> Result<(), ThemeError> {
+        registry.migrate(&self.egui_version, &mut self.style, &mut self.fonts)?;
+        self.egui_version = crate::EGUI_VERSION.to_owned();
+        Ok(())
+    }
+
     /// Consumes the deserialized theme destructively to product the style/font
     pub fn extract(self) -> (Style, FontDefinitions) {
-        let EguiTheme { style, fonts, .. } = self;
-        let style = style::to_style(style);
-        let fonts = fonts::to_fonts(fonts);
+        let (style, fonts, _) = self.extract_with_report();
         (style, fonts)
     }
 
+    /// Consumes the deserialized theme the same way as `extract` but also returns every issue that was found.
+    ///
+    /// Any keys that are unknown, missing or fail to deserialize are replaced by their default values, so an empty list means the theme was loaded exactly as it was saved.
+    ///
+    /// Built-in bases are merged automatically. A `ThemeBase::Theme` base must be merged with `resolve_base` beforehand, otherwise it is reported as unresolved.
+    pub fn extract_with_report(self) -> (Style, FontDefinitions, Vec<ThemeIssue>) {
+        let EguiTheme {
+            style, fonts, base, ..
+        } = base::merge_builtin(self);
+        let (mut style, mut issues) = style::to_style(&style);
+        let (mut fonts, font_issues) = fonts::to_fonts(&fonts);
+        issues.extend(font_issues);
+        // egui panics on unknown fonts and families, so these are always fixed.
+        for reference in references::fix_dangling_references(&mut fonts, &mut style) {
+            let (section, key) = match &reference {
+                DanglingReference::Font { .. } => (ThemeSection::Fonts, "families"),
+                DanglingReference::TextStyle { .. } => (ThemeSection::Style, "text_styles"),
+                DanglingReference::OverrideFont { .. } => (ThemeSection::Style, "override_font_id"),
+            };
+            issues.push(ThemeIssue::new(
+                section,
+                key,
+                ThemeIssueKind::DanglingReference(reference),
+            ));
+        }
+        if let Some(ThemeBase::Theme(name)) = base {
+            issues.insert(
+                0,
+                ThemeIssue::new(ThemeSection::Style, name, ThemeIssueKind::UnresolvedBase),
+            );
+        }
+        (style, fonts, issues)
+    }
+
+    /// Consumes the deserialized theme, failing if any key is unknown, missing or cannot be deserialized.
+    ///
+    /// This is the opt-in strict counterpart to the "best effort" `extract` and guarantees that the returned style/fonts are exactly what was saved,
+    /// including built-in fonts that were removed.
+    pub fn extract_strict(self) -> Result<(Style, FontDefinitions), ThemeError> {
+        let (style, fonts, issues) = self.extract_with_report();
+        if issues.is_empty() {
+            Ok((style, fonts))
+        } else {
+            Err(ThemeError::Strict(issues))
+        }
+    }
+
     pub fn load_into_context(self, context: &mut egui::Context) {
         let (style, fonts) = self.extract();
         context.set_style(style);
@@ -0,0 +1,173 @@
+use egui::{FontData, FontDefinitions, FontFamily, Style, TextStyle};
+use std::fmt;
+
+use super::ThemeError;
+
+/// A reference to a font or family that does not exist. egui panics when laying out text with a dangling reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DanglingReference {
+    /// The family lists a font that is not in `FontDefinitions::font_data`.
+    Font { family: FontFamily, font: String },
+    /// The text style uses a family that is not in `FontDefinitions::families`.
+    TextStyle {
+        text_style: TextStyle,
+        family: FontFamily,
+    },
+    /// `Style::override_font_id` uses a family that is not in `FontDefinitions::families`.
+    OverrideFont { family: FontFamily },
+}
+
+impl fmt::Display for DanglingReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DanglingReference::Font { family, font } => {
+                write!(f, "family `{family}` uses the unknown font `{font}`")
+            }
+            DanglingReference::TextStyle { text_style, family } => {
+                write!(
+                    f,
+                    "text style `{text_style}` uses the unknown family `{family}`"
+                )
+            }
+            DanglingReference::OverrideFont { family } => {
+                write!(f, "the override font uses the unknown family `{family}`")
+            }
+        }
+    }
+}
+
+/// Finds every font and family that is referenced by `fonts` or `style` but does not exist.
+pub fn dangling_references(fonts: &FontDefinitions, style: &Style) -> Vec<DanglingReference> {
+    let mut references = Vec::new();
+    for (family, names) in fonts.families.iter() {
+        for name in names {
+            if !fonts.font_data.contains_key(name) {
+                references.push(DanglingReference::Font {
+                    family: family.to_owned(),
+                    font: name.to_owned(),
+                });
+            }
+        }
+    }
+    for (text_style, font_id) in style.text_styles.iter() {
+        if !fonts.families.contains_key(&font_id.family) {
+            references.push(DanglingReference::TextStyle {
+                text_style: text_style.to_owned(),
+                family: font_id.family.to_owned(),
+            });
+        }
+    }
+    if let Some(font_id) = &style.override_font_id {
+        if !fonts.families.contains_key(&font_id.family) {
+            references.push(DanglingReference::OverrideFont {
+                family: font_id.family.to_owned(),
+            });
+        }
+    }
+    references
+}
+
+/// Removes every dangling reference, returning what was fixed.
+///
+/// Unknown fonts are removed from their families, and text styles using an unknown family are moved to `FontFamily::Proportional`.
+pub fn fix_dangling_references(
+    fonts: &mut FontDefinitions,
+    style: &mut Style,
+) -> Vec<DanglingReference> {
+    let references = dangling_references(fonts, style);
+    if references.is_empty() {
+        return references;
+    }
+    let font_data = &fonts.font_data;
+    for names in fonts.families.values_mut() {
+        names.retain(|name| font_data.contains_key(name));
+    }
+    fonts.families.entry(FontFamily::Proportional).or_default();
+    let font_ids = style
+        .text_styles
+        .values_mut()
+        .chain(style.override_font_id.as_mut());
+    for font_id in font_ids {
+        if !fonts.families.contains_key(&font_id.family) {
+            font_id.family = FontFamily::Proportional;
+        }
+    }
+    references
+}
+
+/// The families that list the font `name`.
+pub fn families_using_font(fonts: &FontDefinitions, name: &str) -> Vec<FontFamily> {
+    fonts
+        .families
+        .iter()
+        .filter(|(_, names)| names.iter().any(|font| font == name))
+        .map(|(family, _)| family.to_owned())
+        .collect()
+}
+
+/// The text styles that use `family`. `Style::override_font_id` is not included.
+pub fn text_styles_using_family(style: &Style, family: &FontFamily) -> Vec<TextStyle> {
+    style
+        .text_styles
+        .iter()
+        .filter(|(_, font_id)| font_id.family == *family)
+        .map(|(text_style, _)| text_style.to_owned())
+        .collect()
+}
+
+/// Removes the font `name` and takes it out of every family.
+pub fn remove_font(fonts: &mut FontDefinitions, name: &str) -> Option<FontData> {
+    let data = fonts.font_data.remove(name)?;
+    for names in fonts.families.values_mut() {
+        names.retain(|font| font != name);
+    }
+    Some(data)
+}
+
+/// Moves the font at `from` in the fallback chain `fonts` so it ends up in the gap before the font at `to`, as when it is
+/// dragged there. A `to` of `fonts.len()` moves it to the end.
+pub fn move_font(fonts: &mut Vec<String>, from: usize, to: usize) {
+    let name = fonts.remove(from);
+    // Taking the font out shifts the fonts after it down by one.
+    let to = if from < to { to - 1 } else { to };
+    fonts.insert(to.min(fonts.len()), name);
+}
+
+/// Removes `family` and moves every text style using it, including the override font, to `replacement`.
+/// Returns the fonts the family listed.
+///
+/// `FontFamily::Proportional` and `FontFamily::Monospace` are used by egui itself and cannot be removed.
+pub fn remove_family(
+    fonts: &mut FontDefinitions,
+    style: &mut Style,
+    family: &FontFamily,
+    replacement: &FontFamily,
+) -> Result<Vec<String>, ThemeError> {
+    if matches!(family, FontFamily::Proportional | FontFamily::Monospace) {
+        return Err(ThemeError::InvalidValue {
+            key: family.to_string(),
+            error: "the built-in families cannot be removed".to_owned(),
+        });
+    }
+    if family == replacement {
+        return Err(ThemeError::InvalidValue {
+            key: replacement.to_string(),
+            error: "a family cannot replace itself".to_owned(),
+        });
+    }
+    if !fonts.families.contains_key(replacement) {
+        return Err(ThemeError::MissingKey(replacement.to_string()));
+    }
+    let names = fonts
+        .families
+        .remove(family)
+        .ok_or_else(|| ThemeError::MissingKey(family.to_string()))?;
+    let font_ids = style
+        .text_styles
+        .values_mut()
+        .chain(style.override_font_id.as_mut());
+    for font_id in font_ids.filter(|font_id| font_id.family == *family) {
+        font_id.family = replacement.to_owned();
+    }
+    Ok(names)
+}
@@ -0,0 +1,60 @@
+use egui::{FontDefinitions, FontFamily, Style};
+
+use super::ThemeError;
+
+/// Renames the font `from` to `to`, keeping its position in every family it is in.
+///
+/// Fails if there is no font `from` or if a font named `to` already exists.
+pub fn rename_font(fonts: &mut FontDefinitions, from: &str, to: &str) -> Result<(), ThemeError> {
+    if !fonts.font_data.contains_key(from) {
+        return Err(ThemeError::MissingKey(from.to_owned()));
+    }
+    if from == to {
+        return Ok(());
+    }
+    if fonts.font_data.contains_key(to) {
+        return Err(ThemeError::NameTaken(to.to_owned()));
+    }
+    if let Some(data) = fonts.font_data.remove(from) {
+        fonts.font_data.insert(to.to_owned(), data);
+    }
+    for name in fonts.families.values_mut().flatten() {
+        if name == from {
+            *name = to.to_owned();
+        }
+    }
+    Ok(())
+}
+
+/// Renames the family `FontFamily::Name(from)` to `FontFamily::Name(to)`, updating every text style of `style` that uses it.
+///
+/// Fails if there is no family `from` or if a family named `to` already exists.
+pub fn rename_family(
+    fonts: &mut FontDefinitions,
+    style: &mut Style,
+    from: &str,
+    to: &str,
+) -> Result<(), ThemeError> {
+    let from = FontFamily::Name(from.into());
+    let to = FontFamily::Name(to.into());
+    if !fonts.families.contains_key(&from) {
+        return Err(ThemeError::MissingKey(from.to_string()));
+    }
+    if from == to {
+        return Ok(());
+    }
+    if fonts.families.contains_key(&to) {
+        return Err(ThemeError::NameTaken(to.to_string()));
+    }
+    if let Some(names) = fonts.families.remove(&from) {
+        fonts.families.insert(to.clone(), names);
+    }
+    let font_ids = style
+        .text_styles
+        .values_mut()
+        .chain(style.override_font_id.as_mut());
+    for font_id in font_ids.filter(|font_id| font_id.family == from) {
+        font_id.family = to.clone();
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::DanglingReference;
+
+/// The section of the `EguiTheme` that a `ThemeIssue` was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeSection {
+    Style,
+    Fonts,
+}
+
+impl fmt::Display for ThemeSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeSection::Style => write!(f, "style"),
+            ThemeSection::Fonts => write!(f, "fonts"),
+        }
+    }
+}
+
+/// Describes what went wrong with a single key during extraction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeIssueKind {
+    /// The key is not recognised by this version of egui-theme and was ignored.
+    UnknownKey,
+    /// The key exists but the value could not be deserialized into the expected type.
+    /// The default value was used in its place.
+    InvalidValue(String),
+    /// The key was not present in the theme so the default value was used.
+    MissingKey,
+    /// The theme inherits from the named base theme which was not resolved before extracting.
+    UnresolvedBase,
+    /// The named font is referenced by a `FontSource` which was not resolved before extracting.
+    /// The font is left out of the font families.
+    UnresolvedFont,
+    /// The data of the named font does not match the hash it was saved with, e.g. because the theme file was truncated.
+    /// The font is left out of the font definitions and families.
+    CorruptFont,
+    /// The data of the named font is not a font egui can use. The font is left out of the font definitions and families.
+    InvalidFont(String),
+    /// The key references a font or family that does not exist. The reference was removed, or moved to `FontFamily::Proportional` for text styles.
+    DanglingReference(DanglingReference),
+}
+
+/// A problem found with a single key while extracting an `EguiTheme`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeIssue {
+    pub section: ThemeSection,
+    pub key: String,
+    pub kind: ThemeIssueKind,
+}
+
+impl ThemeIssue {
+    pub(crate) fn new(section: ThemeSection, key: impl Into<String>, kind: ThemeIssueKind) -> Self {
+        Self {
+            section,
+            key: key.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ThemeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ThemeIssueKind::UnknownKey => write!(f, "{}: unknown key `{}`", self.section, self.key),
+            ThemeIssueKind::InvalidValue(error) => {
+                write!(
+                    f,
+                    "{}: invalid value for `{}`: {}",
+                    self.section, self.key, error
+                )
+            }
+            ThemeIssueKind::MissingKey => write!(f, "{}: missing key `{}`", self.section, self.key),
+            ThemeIssueKind::UnresolvedBase => {
+                write!(f, "base theme `{}` was not resolved", self.key)
+            }
+            ThemeIssueKind::UnresolvedFont => {
+                write!(f, "{}: font `{}` was not resolved", self.section, self.key)
+            }
+            ThemeIssueKind::CorruptFont => {
+                write!(
+                    f,
+                    "{}: the data of font `{}` does not match its hash",
+                    self.section, self.key
+                )
+            }
+            ThemeIssueKind::InvalidFont(error) => {
+                write!(
+                    f,
+                    "{}: font `{}` is invalid: {}",
+                    self.section, self.key, error
+                )
+            }
+            ThemeIssueKind::DanglingReference(reference) => {
+                write!(f, "{}: `{}`: {}", self.section, self.key, reference)
+            }
+        }
+    }
+}
+
+/// Tracks which keys of a section have been read and records any issues found along the way.
+pub(crate) struct Reader<'a> {
+    section: ThemeSection,
+    hash_map: &'a HashMap<String, super::ThemeValue>,
+    visited: Vec<&'static str>,
+    issues: Vec<ThemeIssue>,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(
+        section: ThemeSection,
+        hash_map: &'a HashMap<String, super::ThemeValue>,
+    ) -> Self {
+        Self {
+            section,
+            hash_map,
+            visited: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    /// Returns the raw value for `key`, recording it as missing if it does not exist.
+    pub(crate) fn value(&mut self, key: &'static str) -> Option<&'a super::ThemeValue> {
+        self.visited.push(key);
+        let value = self.hash_map.get(key);
+        if value.is_none() {
+            self.issues.push(ThemeIssue::new(
+                self.section,
+                key,
+                ThemeIssueKind::MissingKey,
+            ));
+        }
+        value
+    }
+
+    /// Deserializes the value for `key`, recording any failure.
+    pub(crate) fn read<T: serde::de::DeserializeOwned>(&mut self, key: &'static str) -> Option<T> {
+        let value = self.value(key)?;
+        self.decode(key, value)
+    }
+
+    /// Deserializes the value for `key` if it exists. Unlike `read`, a missing key is not an issue.
+    pub(crate) fn read_optional<T: serde::de::DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+    ) -> Option<T> {
+        self.visited.push(key);
+        let value = self.hash_map.get(key)?;
+        self.decode(key, value)
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+        value: &super::ThemeValue,
+    ) -> Option<T> {
+        match serde_json::from_value(value.to_owned()) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.invalid(key, error);
+                None
+            }
+        }
+    }
+
+    /// Records an issue with `key`.
+    pub(crate) fn issue(&mut self, key: &str, kind: ThemeIssueKind) {
+        self.issues.push(ThemeIssue::new(self.section, key, kind));
+    }
+
+    /// Records that the value for `key` could not be deserialized.
+    pub(crate) fn invalid(&mut self, key: &str, error: impl fmt::Display) {
+        self.issues.push(ThemeIssue::new(
+            self.section,
+            key,
+            ThemeIssueKind::InvalidValue(error.to_string()),
+        ));
+    }
+
+    /// Consumes the reader, flagging every key that was never read as unknown.
+    pub(crate) fn finish(mut self) -> Vec<ThemeIssue> {
+        let mut unknown = self
+            .hash_map
+            .keys()
+            .filter(|key| !self.visited.contains(&key.as_str()))
+            .collect::<Vec<_>>();
+        // HashMap iteration order is random, sort to keep the reports stable.
+        unknown.sort();
+        for key in unknown {
+            self.issues.push(ThemeIssue::new(
+                self.section,
+                key.as_str(),
+                ThemeIssueKind::UnknownKey,
+            ));
+        }
+        self.issues
+    }
+}
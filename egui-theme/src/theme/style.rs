@@ -1,6 +1,8 @@
 use egui::Style;
 use std::collections::HashMap;
 
+use super::report::{Reader, ThemeIssue, ThemeSection};
+
 const TEXT_STYLES_KEY: &str = "text_styles";
 
 // TODO: Change the println! to a proper logging crate.
@@ -28,21 +30,15 @@ macro_rules! ser {
 }
 
 macro_rules! de {
-    ($collection:ident, $style:ident, $prop:ident) => {
-        $collection.get(&stringify!($prop).to_owned()).map(|value| {
-            if let Ok(deserialized_value) = serde_json::from_value(value.to_owned()) {
-                $style.$prop = deserialized_value;
-            }
-        });
+    ($reader:ident, $style:ident, $prop:ident) => {
+        if let Some(value) = $reader.read(stringify!($prop)) {
+            $style.$prop = value;
+        }
     };
-    ($collection:ident, $style:ident, $prop:ident, $sub_prop:ident) => {
-        $collection
-            .get(&stringify!($prop.$sub_prop).to_owned())
-            .map(|value| {
-                if let Ok(deserialized_value) = serde_json::from_value(value.to_owned()) {
-                    $style.$prop.$sub_prop = deserialized_value;
-                }
-            });
+    ($reader:ident, $style:ident, $prop:ident, $sub_prop:ident) => {
+        if let Some(value) = $reader.read(stringify!($prop.$sub_prop)) {
+            $style.$prop.$sub_prop = value;
+        }
     };
 }
 
@@ -69,6 +65,7 @@ pub fn from_style(style: Style) -> HashMap<String, super::ThemeValue> {
 
     ser!(hash_map, style, animation_time);
     ser!(hash_map, style, explanation_tooltips);
+    ser!(hash_map, style, debug);
 
     ser!(hash_map, style, spacing, item_spacing);
     ser!(hash_map, style, spacing, window_margin);
@@ -78,6 +75,7 @@ pub fn from_style(style: Style) -> HashMap<String, super::ThemeValue> {
     ser!(hash_map, style, spacing, slider_width);
     ser!(hash_map, style, spacing, text_edit_width);
     ser!(hash_map, style, spacing, icon_width);
+    ser!(hash_map, style, spacing, icon_width_inner);
     ser!(hash_map, style, spacing, icon_spacing);
     ser!(hash_map, style, spacing, tooltip_width);
     ser!(hash_map, style, spacing, indent_ends_with_horizontal_line);
@@ -109,63 +107,60 @@ pub fn from_style(style: Style) -> HashMap<String, super::ThemeValue> {
 }
 
 /// Helper function to deserialize the `egui::Style`
-pub fn to_style(hash_map: HashMap<String, super::ThemeValue>) -> Style {
+/// Any keys that are missing, unknown or fail to deserialize are reported alongside the resulting style.
+pub fn to_style(hash_map: &HashMap<String, super::ThemeValue>) -> (Style, Vec<ThemeIssue>) {
     let mut style = Style::default();
+    let mut reader = Reader::new(ThemeSection::Style, hash_map);
     // Special case due to json requiring String keys
-    {
-        if let Some(value) = hash_map.get(TEXT_STYLES_KEY) {
-            if let Ok(values) =
-                serde_json::from_value::<Vec<(egui::TextStyle, egui::FontId)>>(value.to_owned())
-            {
-                for (key, value) in values {
-                    style.text_styles.insert(key, value);
-                }
-            }
-        }
+    // The stored text styles replace the defaults, so text styles that were removed stay removed.
+    if let Some(values) = reader.read::<Vec<(egui::TextStyle, egui::FontId)>>(TEXT_STYLES_KEY) {
+        style.text_styles = values.into_iter().collect();
     }
 
-    de!(hash_map, style, override_text_style);
-    de!(hash_map, style, override_font_id);
-    de!(hash_map, style, wrap);
-
-    de!(hash_map, style, animation_time);
-    de!(hash_map, style, explanation_tooltips);
-
-    de!(hash_map, style, spacing, item_spacing);
-    de!(hash_map, style, spacing, window_margin);
-    de!(hash_map, style, spacing, button_padding);
-    de!(hash_map, style, spacing, indent);
-    de!(hash_map, style, spacing, interact_size);
-    de!(hash_map, style, spacing, slider_width);
-    de!(hash_map, style, spacing, text_edit_width);
-    de!(hash_map, style, spacing, icon_width);
-    de!(hash_map, style, spacing, icon_spacing);
-    de!(hash_map, style, spacing, tooltip_width);
-    de!(hash_map, style, spacing, indent_ends_with_horizontal_line);
-    de!(hash_map, style, spacing, combo_height);
-    de!(hash_map, style, spacing, scroll_bar_width);
-
-    de!(hash_map, style, interaction, resize_grab_radius_side);
-    de!(hash_map, style, interaction, resize_grab_radius_corner);
-    de!(hash_map, style, interaction, show_tooltips_only_when_still);
-
-    de!(hash_map, style, visuals, dark_mode);
-    de!(hash_map, style, visuals, override_text_color);
-    de!(hash_map, style, visuals, widgets);
-    de!(hash_map, style, visuals, selection);
-    de!(hash_map, style, visuals, hyperlink_color);
-    de!(hash_map, style, visuals, faint_bg_color);
-    de!(hash_map, style, visuals, extreme_bg_color);
-    de!(hash_map, style, visuals, code_bg_color);
-    de!(hash_map, style, visuals, window_rounding);
-    de!(hash_map, style, visuals, window_shadow);
-    de!(hash_map, style, visuals, popup_shadow);
-    de!(hash_map, style, visuals, resize_corner_size);
-    de!(hash_map, style, visuals, text_cursor_width);
-    de!(hash_map, style, visuals, text_cursor_preview);
-    de!(hash_map, style, visuals, clip_rect_margin);
-    de!(hash_map, style, visuals, button_frame);
-    de!(hash_map, style, visuals, collapsing_header_frame);
-
-    style
+    de!(reader, style, override_text_style);
+    de!(reader, style, override_font_id);
+    de!(reader, style, wrap);
+
+    de!(reader, style, animation_time);
+    de!(reader, style, explanation_tooltips);
+    de!(reader, style, debug);
+
+    de!(reader, style, spacing, item_spacing);
+    de!(reader, style, spacing, window_margin);
+    de!(reader, style, spacing, button_padding);
+    de!(reader, style, spacing, indent);
+    de!(reader, style, spacing, interact_size);
+    de!(reader, style, spacing, slider_width);
+    de!(reader, style, spacing, text_edit_width);
+    de!(reader, style, spacing, icon_width);
+    de!(reader, style, spacing, icon_width_inner);
+    de!(reader, style, spacing, icon_spacing);
+    de!(reader, style, spacing, tooltip_width);
+    de!(reader, style, spacing, indent_ends_with_horizontal_line);
+    de!(reader, style, spacing, combo_height);
+    de!(reader, style, spacing, scroll_bar_width);
+
+    de!(reader, style, interaction, resize_grab_radius_side);
+    de!(reader, style, interaction, resize_grab_radius_corner);
+    de!(reader, style, interaction, show_tooltips_only_when_still);
+
+    de!(reader, style, visuals, dark_mode);
+    de!(reader, style, visuals, override_text_color);
+    de!(reader, style, visuals, widgets);
+    de!(reader, style, visuals, selection);
+    de!(reader, style, visuals, hyperlink_color);
+    de!(reader, style, visuals, faint_bg_color);
+    de!(reader, style, visuals, extreme_bg_color);
+    de!(reader, style, visuals, code_bg_color);
+    de!(reader, style, visuals, window_rounding);
+    de!(reader, style, visuals, window_shadow);
+    de!(reader, style, visuals, popup_shadow);
+    de!(reader, style, visuals, resize_corner_size);
+    de!(reader, style, visuals, text_cursor_width);
+    de!(reader, style, visuals, text_cursor_preview);
+    de!(reader, style, visuals, clip_rect_margin);
+    de!(reader, style, visuals, button_frame);
+    de!(reader, style, visuals, collapsing_header_frame);
+
+    (style, reader.finish())
 }
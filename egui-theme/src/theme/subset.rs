@@ -0,0 +1,183 @@
+use egui::{FontData, FontDefinitions};
+use std::collections::BTreeMap;
+
+use super::default_fonts::is_builtin_font_data;
+use super::font_info::parse;
+use super::{FontError, ThemeError, UNICODE_RANGES};
+
+/// The characters of Basic Latin and the Latin-1 Supplement, enough for most western European UI strings.
+pub fn latin1_chars() -> impl Iterator<Item = char> {
+    UNICODE_RANGES[..2].iter().flat_map(|range| range.chars())
+}
+
+/// Reduces the font face at `index` to the glyphs needed for `chars`.
+///
+/// Glyphs that are not needed keep their id but lose their outline, and the `cmap` is rewritten to only map `chars`,
+/// so egui falls back to the next font of the family for every other character.
+/// Layout tables such as `GSUB` and `GPOS` are dropped. The result is always a single font, so the index of the subset face is 0.
+pub fn subset_font(
+    bytes: &[u8],
+    index: u32,
+    chars: impl IntoIterator<Item = char>,
+) -> Result<Vec<u8>, FontError> {
+    let face = parse(bytes, index)?;
+    let mapping = chars
+        .into_iter()
+        .filter_map(|c| {
+            let glyph = face.glyph_index(c).filter(|glyph| glyph.0 != 0)?;
+            Some((u32::from(c), glyph.0))
+        })
+        .collect::<BTreeMap<_, _>>();
+    // The `.notdef` glyph must always be kept.
+    let mut glyphs = mapping.values().copied().collect::<Vec<_>>();
+    glyphs.push(0);
+    glyphs.sort_unstable();
+    glyphs.dedup();
+
+    let subset = subsetter::subset(bytes, index, subsetter::Profile::pdf(&glyphs))
+        .map_err(|error| FontError::Parse(error.to_string()))?;
+    replace_table(&subset, *b"cmap", &cmap(&mapping))
+}
+
+/// Subsets every font of `fonts` with `subset_font`, leaving the families untouched.
+///
+/// Use this right before creating the theme to keep large fonts, such as CJK fonts, out of themes for applications that only render a few scripts.
+///
+/// ```ignore
+/// let chars = latin1_chars().chain("→✔".chars()).collect::<Vec<_>>();
+/// let theme = EguiTheme::new(style, subset_fonts(font_definitions, chars)?);
+/// ```
+pub fn subset_fonts(
+    mut fonts: FontDefinitions,
+    chars: impl IntoIterator<Item = char>,
+) -> Result<FontDefinitions, ThemeError> {
+    let chars = chars.into_iter().collect::<Vec<_>>();
+    for (name, data) in fonts.font_data.iter_mut() {
+        // The built-in fonts are never written to the theme.
+        if is_builtin_font_data(name, data) {
+            continue;
+        }
+        let font = subset_font(&data.font, data.index, chars.iter().copied()).map_err(|error| {
+            ThemeError::InvalidValue {
+                key: name.to_owned(),
+                error: error.to_string(),
+            }
+        })?;
+        *data = FontData {
+            index: 0,
+            tweak: data.tweak,
+            ..FontData::from_owned(font)
+        };
+    }
+    Ok(fonts)
+}
+
+/// A `cmap` with a single Windows Unicode full repertoire (format 12) subtable.
+fn cmap(mapping: &BTreeMap<u32, u16>) -> Vec<u8> {
+    // Consecutive characters mapped to consecutive glyphs are stored as one group.
+    let mut groups: Vec<(u32, u32, u32)> = Vec::new();
+    for (&c, &glyph) in mapping {
+        match groups.last_mut() {
+            Some((start, end, start_glyph))
+                if c == *end + 1 && u32::from(glyph) == *start_glyph + (c - *start) =>
+            {
+                *end = c
+            }
+            _ => groups.push((c, c, u32::from(glyph))),
+        }
+    }
+
+    let mut table = Vec::with_capacity(28 + 12 * groups.len());
+    // Header with one encoding record, the subtable directly follows it.
+    table.extend_from_slice(&0u16.to_be_bytes());
+    table.extend_from_slice(&1u16.to_be_bytes());
+    table.extend_from_slice(&3u16.to_be_bytes());
+    table.extend_from_slice(&10u16.to_be_bytes());
+    table.extend_from_slice(&12u32.to_be_bytes());
+    // Format 12 subtable.
+    table.extend_from_slice(&12u16.to_be_bytes());
+    table.extend_from_slice(&0u16.to_be_bytes());
+    table.extend_from_slice(&(16 + 12 * groups.len() as u32).to_be_bytes());
+    table.extend_from_slice(&0u32.to_be_bytes());
+    table.extend_from_slice(&(groups.len() as u32).to_be_bytes());
+    for (start, end, start_glyph) in groups {
+        table.extend_from_slice(&start.to_be_bytes());
+        table.extend_from_slice(&end.to_be_bytes());
+        table.extend_from_slice(&start_glyph.to_be_bytes());
+    }
+    table
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, FontError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes(bytes.try_into().expect("the slice has 4 bytes")))
+        .ok_or_else(|| FontError::Parse("truncated table directory".to_owned()))
+}
+
+/// Sum of the data as big endian u32s, padded with zeros to a multiple of 4 bytes.
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut bytes = [0; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(bytes))
+    })
+}
+
+/// Rebuilds the single font `font` with the table `tag` replaced by `data`, fixing up offsets and checksums.
+fn replace_table(font: &[u8], tag: [u8; 4], data: &[u8]) -> Result<Vec<u8>, FontError> {
+    let count = font
+        .get(4..6)
+        .map(|count| u16::from_be_bytes([count[0], count[1]]) as usize)
+        .ok_or_else(|| FontError::Parse("truncated font header".to_owned()))?;
+    let mut tables = Vec::with_capacity(count);
+    for i in 0..count {
+        let record = 12 + 16 * i;
+        let table_tag = font
+            .get(record..record + 4)
+            .ok_or_else(|| FontError::Parse("truncated table directory".to_owned()))?;
+        let offset = read_u32(font, record + 8)? as usize;
+        let length = read_u32(font, record + 12)? as usize;
+        let table = if table_tag == tag {
+            data
+        } else {
+            font.get(offset..offset + length)
+                .ok_or_else(|| FontError::Parse("table out of bounds".to_owned()))?
+        };
+        tables.push((
+            <[u8; 4]>::try_from(table_tag).expect("tags have 4 bytes"),
+            table,
+        ));
+    }
+
+    let mut output = font[..12].to_vec();
+    let mut offset = 12 + 16 * count;
+    let mut head = None;
+    for (table_tag, table) in tables.iter() {
+        let checksum = if table_tag == b"head" && table.len() >= 12 {
+            head = Some(offset);
+            // The checksum of `head` is calculated with a zeroed checksum adjustment.
+            let mut table = table.to_vec();
+            table[8..12].fill(0);
+            checksum(&table)
+        } else {
+            checksum(table)
+        };
+        output.extend_from_slice(table_tag);
+        output.extend_from_slice(&checksum.to_be_bytes());
+        output.extend_from_slice(&(offset as u32).to_be_bytes());
+        output.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        offset += (table.len() + 3) & !3;
+    }
+    for (_, table) in tables.iter() {
+        output.extend_from_slice(table);
+        output.resize((output.len() + 3) & !3, 0);
+    }
+
+    if let Some(head) = head {
+        output[head + 8..head + 12].fill(0);
+        let adjustment = 0xB1B0_AFBA_u32.wrapping_sub(checksum(&output));
+        output[head + 8..head + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    Ok(output)
+}
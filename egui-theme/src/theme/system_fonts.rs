@@ -0,0 +1,249 @@
+use egui::FontData;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::font_info::{face_name, parse};
+use super::{validate_font, FontError};
+
+/// The extensions of the font files egui can load.
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// A font face installed on the host, found by `system_fonts` or `scan_font_dirs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemFont {
+    pub path: PathBuf,
+    /// The index of the face in the file. Only font collections have more than one face.
+    pub index: u32,
+    /// The family name stored in the font, or the file name if it has none.
+    pub family_name: String,
+    /// The style name stored in the font, such as `Bold Italic`.
+    pub style_name: Option<String>,
+}
+
+impl SystemFont {
+    /// A name to register the font under in `FontDefinitions::font_data`, such as `Noto Sans Bold`.
+    pub fn name(&self) -> String {
+        match self.style_name.as_deref() {
+            Some(style) if !style.is_empty() && style != "Regular" => {
+                format!("{} {style}", self.family_name)
+            }
+            _ => self.family_name.clone(),
+        }
+    }
+
+    /// Reads the font file, making sure egui can use the face before it is added to `FontDefinitions`.
+    pub fn load(&self) -> Result<FontData, FontError> {
+        let bytes = fs::read(&self.path).map_err(|error| FontError::Io(error.to_string()))?;
+        validate_font(&bytes, self.index)?;
+        Ok(FontData {
+            index: self.index,
+            ..FontData::from_owned(bytes)
+        })
+    }
+}
+
+/// The directories fonts are installed to on Linux and other Unix systems.
+///
+/// Like fontconfig, these are the XDG data directories, `~/.fonts` and the `<dir>` entries of the fontconfig configuration
+/// in `/etc/fonts` and `$XDG_CONFIG_HOME/fontconfig`. Directories that do not exist are left out.
+pub fn system_font_dirs() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let xdg_dir = |var: &str, default: &str| {
+        env::var_os(var)
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home.as_ref().map(|home| home.join(default)))
+    };
+    let data_home = xdg_dir("XDG_DATA_HOME", ".local/share");
+    let config_home = xdg_dir("XDG_CONFIG_HOME", ".config");
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .map(|dirs| env::split_paths(&dirs).collect::<Vec<_>>())
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| vec!["/usr/local/share".into(), "/usr/share".into()]);
+
+    let mut dirs = Vec::new();
+    dirs.extend(data_home.iter().map(|dir| dir.join("fonts")));
+    dirs.extend(home.iter().map(|home| home.join(".fonts")));
+    dirs.extend(data_dirs.iter().map(|dir| dir.join("fonts")));
+
+    let mut configs = vec![
+        PathBuf::from("/etc/fonts/fonts.conf"),
+        PathBuf::from("/etc/fonts/local.conf"),
+    ];
+    if let Ok(entries) = fs::read_dir("/etc/fonts/conf.d") {
+        let mut entries = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "conf")
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        configs.extend(entries);
+    }
+    configs.extend(
+        config_home
+            .iter()
+            .map(|dir| dir.join("fontconfig/fonts.conf")),
+    );
+    for config in configs.iter() {
+        if let Ok(conf) = fs::read_to_string(config) {
+            dirs.extend(fontconfig_dirs(
+                &conf,
+                config.parent(),
+                home.as_deref(),
+                data_home.as_deref(),
+            ));
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    dirs.retain(|dir| dir.is_dir() && seen.insert(dir.clone()));
+    dirs
+}
+
+/// The `<dir>` entries of a fontconfig configuration file, resolved like fontconfig does.
+///
+/// `prefix="xdg"` entries are relative to `data_home`, `prefix="relative"` entries to `conf_dir`, and a leading `~` is `home`.
+/// Entries that do not resolve to an absolute path are left out.
+pub(crate) fn fontconfig_dirs(
+    conf: &str,
+    conf_dir: Option<&Path>,
+    home: Option<&Path>,
+    data_home: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut rest = conf;
+    while let Some(start) = rest.find("<dir") {
+        // Commented out entries are skipped.
+        if let Some(comment) = rest.find("<!--").filter(|comment| *comment < start) {
+            rest = match rest[comment..].find("-->") {
+                Some(end) => &rest[comment + end + 3..],
+                None => break,
+            };
+            continue;
+        }
+        rest = &rest[start + 4..];
+        let (tag_end, end) = match (rest.find('>'), rest.find("</dir>")) {
+            (Some(tag_end), Some(end)) if tag_end < end => (tag_end, end),
+            _ => continue,
+        };
+        let attributes = &rest[..tag_end];
+        // Other elements starting with `dir`, and empty `<dir/>` elements.
+        if !attributes.is_empty() && !attributes.starts_with(char::is_whitespace)
+            || attributes.ends_with('/')
+        {
+            continue;
+        }
+        let path = rest[tag_end + 1..end].trim();
+        rest = &rest[end..];
+        let prefix = attributes
+            .split_whitespace()
+            .find_map(|attribute| attribute.strip_prefix("prefix="))
+            .map(|prefix| prefix.trim_matches(|c| c == '"' || c == '\''));
+        let dir = match prefix {
+            Some("xdg") => data_home.map(|dir| dir.join(path)),
+            Some("relative") => conf_dir.map(|dir| dir.join(path)),
+            _ => match path.strip_prefix('~') {
+                Some(path) => home.map(|home| home.join(path.trim_start_matches('/'))),
+                None => Some(PathBuf::from(path)),
+            },
+        };
+        dirs.extend(dir.filter(|dir| dir.is_absolute()));
+    }
+    dirs
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            FONT_EXTENSIONS
+                .iter()
+                .any(|font| font.eq_ignore_ascii_case(extension))
+        })
+}
+
+/// Every face of the font file at `path` that egui can use.
+fn read_faces(path: &Path) -> Vec<SystemFont> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => return Vec::new(),
+    };
+    let count = ttf_parser::fonts_in_collection(&bytes).unwrap_or(1);
+    (0..count)
+        .filter_map(|index| {
+            validate_font(&bytes, index).ok()?;
+            let face = parse(&bytes, index).ok()?;
+            use ttf_parser::name_id;
+            let family_name = face_name(&face, &[name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY])
+                .or_else(|| Some(path.file_stem()?.to_string_lossy().into_owned()))?;
+            Some(SystemFont {
+                path: path.to_owned(),
+                index,
+                family_name,
+                style_name: face_name(&face, &[name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY]),
+            })
+        })
+        .collect()
+}
+
+/// Finds every font face egui can use in `dirs` and their subdirectories, sorted by family and style name.
+///
+/// Files that cannot be read or parsed are skipped. Font collections (`.ttc`, `.otc`) yield a `SystemFont` per face.
+pub fn scan_font_dirs<P: AsRef<Path>>(dirs: impl IntoIterator<Item = P>) -> Vec<SystemFont> {
+    let mut pending = dirs
+        .into_iter()
+        .map(|dir| dir.as_ref().to_owned())
+        .collect::<Vec<_>>();
+    let mut visited = BTreeSet::new();
+    let mut files = BTreeSet::new();
+    while let Some(dir) = pending.pop() {
+        // Symbolic links and nested font directories can make a directory show up more than once, or even inside itself.
+        let dir = match dir.canonicalize() {
+            Ok(dir) => dir,
+            Err(_) => continue,
+        };
+        if !visited.insert(dir.clone()) {
+            continue;
+        }
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_font_file(&path) {
+                files.insert(path.canonicalize().unwrap_or(path));
+            }
+        }
+    }
+    let mut fonts = files
+        .iter()
+        .flat_map(|path| read_faces(path))
+        .collect::<Vec<_>>();
+    fonts.sort_by_cached_key(|font| {
+        (
+            font.family_name.to_lowercase(),
+            font.style_name.clone(),
+            font.path.clone(),
+            font.index,
+        )
+    });
+    fonts
+}
+
+/// Every font installed on the host, found by scanning `system_font_dirs`.
+///
+/// Every font file is read to get its names, so this can take a moment on systems with many fonts.
+/// Interactive applications should call it from a background thread.
+pub fn system_fonts() -> Vec<SystemFont> {
+    scan_font_dirs(system_font_dirs())
+}